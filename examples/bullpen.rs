@@ -1,7 +1,7 @@
 use std::f32::consts::PI;
 
 use bevy::{
-    diagnostic::LogDiagnosticsPlugin, input::common_conditions::input_just_released, math::DVec3,
+    diagnostic::LogDiagnosticsPlugin, input::common_conditions::input_just_released,
     pbr::CascadeShadowConfigBuilder, prelude::*, window::WindowResolution,
 };
 use bevy_flycam::{FlyCam, NoCameraPlayerPlugin};
@@ -48,9 +48,13 @@ fn main() {
     app.add_plugins(BlenvyPlugin::default());
     app.add_plugins(NoCameraPlayerPlugin);
     app.add_plugins(BaseballFlightPlugin {
-        ssw_on: true,
-        magnus_on: true,
-        drag_on: true,
+        config: BaseballPluginConfig {
+            ssw_on: true,
+            magnus_on: true,
+            drag_on: true,
+            ..default()
+        },
+        ..default()
     });
 
     app.add_systems(PostStartup, (setup_scene, spawn_camera.after(setup_scene)));
@@ -151,114 +155,56 @@ fn setup_scene(
 
 fn spawn_ball(
     mut commands: Commands,
-    mut ev_activate_aerodynamics: EventWriter<ActivateAerodynamicsEvent>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
 ) {
-    let gyro_pole = GyroPole::default();
-    let spin_efficiency: f32 = 1.0;
-    let velocity: f32 = 96. * MPH_TO_FTS;
-    let spin_rate: f32 = 2400.;
-    let seam_z_angle: f32 = PI / 2.;
     let tilt = match Tilt::from_hour_mintes(12, 0) {
         Ok(tilt_) => tilt_,
         Err(e) => {
             panic!("tilt calculation panic: {}", e);
         }
     };
-
-    let fixed_spin_rate = if spin_rate == 0. { 1. } else { spin_rate };
-
-    let gyro = match gyro_pole {
-        GyroPole::Left => spin_efficiency.asin(),
-        GyroPole::Right => PI - spin_efficiency.asin(),
+    let release = match PitchRelease::builder()
+        .release_point(Vec3::new(-0.48, 16.764, 1.82))
+        .velocity(96.)
+        .spin_rate(2400.)
+        .tilt(tilt)
+        .spin_efficiency(1.0)
+        .gyro_pole(GyroPole::default())
+        .seam_orientation(SeamOrientation::FourSeam)
+        .build()
+    {
+        Ok(release_) => release_,
+        Err(e) => {
+            panic!("pitch release panic: {}", e);
+        }
     };
 
-    let spin_x_0 = fixed_spin_rate * (spin_efficiency * tilt.get().sin());
-    let spin_y_0 = fixed_spin_rate * gyro.cos(); // ((1. - spin_efficiency.powi(2)).sqrt());
-    let spin_z_0 = -fixed_spin_rate * (spin_efficiency * tilt.get().cos());
-    let spin = Vec3::new(
-        spin_x_0 * RPM_TO_RADS,
-        spin_y_0 * RPM_TO_RADS, // - RPM_TO_RAD ???
-        spin_z_0 * RPM_TO_RADS,
-    );
-
-    // let entity = commands
-    //     .spawn((
-    //         Name::new("ball"),
-    //         //
-    //         BaseballFlightBundle::default(),
-    //         //
-    //         ExternalForce::default(),
-    //         TransformBundle::from_transform(Transform::from_translation(Vec3::new(
-    //             0.48, 1.82, 16.764,
-    //         ))),
-    //         Velocity {
-    //             linvel: (-Vec3::Y * velocity).from_baseball_coord_to_bevy(),
-    //             angvel: spin.from_baseball_coord_to_bevy(),
-    //         },
-    //         //
-    //         Restitution {
-    //             coefficient: 0.546,
-    //             combine_rule: CoefficientCombineRule::Min,
-    //         },
-    //         //
-    //         InheritedVisibility::VISIBLE,
-    //     ))
-    //     .with_children(|child| {
-    //         child.spawn((PbrBundle {
-    //             mesh: meshes.add(Sphere::new(0.03).mesh().uv(32, 18)),
-    //             material: materials.add(StandardMaterial {
-    //                 base_color: Color::BLACK,
-    //                 perceptual_roughness: 1.0,
-    //                 ..default()
-    //             }),
-    //             ..default()
-    //         },));
-    //     })
-    //     .id();
-
-    ev_activate_aerodynamics.send(ActivateAerodynamicsEvent {
-        // entity,
-        seam_y_angle: 0.,
-        seam_z_angle,
-        record_times: vec![0.1, 0.2, 0.3],
-        strikezone_panels_z: (0., 0.),
-        entity: {
-            commands
-                .spawn((
-                    Name::new("ball"),
-                    //
-                    BaseballFlightBundle::default(),
-                    //
-                    ExternalForce::default(),
-                    TransformBundle::from_transform(Transform::from_translation(Vec3::new(
-                        0.48, 1.82, 16.764,
-                    ))),
-                    Velocity {
-                        linvel: (-Vec3::Y * velocity).from_baseball_coord_to_bevy(),
-                        angvel: spin.from_baseball_coord_to_bevy(),
-                    },
-                    //
-                    Restitution {
-                        coefficient: 0.546,
-                        combine_rule: CoefficientCombineRule::Min,
-                    },
-                    //
-                    InheritedVisibility::VISIBLE,
-                ))
-                .with_children(|child| {
-                    child.spawn((PbrBundle {
-                        mesh: meshes.add(Sphere::new(0.03).mesh().uv(32, 18)),
-                        material: materials.add(StandardMaterial {
-                            base_color: Color::BLACK,
-                            perceptual_roughness: 1.0,
-                            ..default()
-                        }),
+    commands
+        .spawn_pitch(release)
+        .insert((
+            Name::new("ball"),
+            //
+            Restitution {
+                coefficient: 0.546,
+                combine_rule: CoefficientCombineRule::Min,
+            },
+            //
+            InheritedVisibility::VISIBLE,
+        ))
+        .with_children(|child| {
+            child.spawn((
+                PbrBundle {
+                    mesh: meshes.add(baseball_mesh(BallProperties::default().radius)),
+                    material: materials.add(StandardMaterial {
+                        base_color_texture: Some(images.add(baseball_texture(256, 128))),
+                        perceptual_roughness: 1.0,
                         ..default()
-                    },));
-                })
-                .id()
-        },
-    });
+                    }),
+                    ..default()
+                },
+                BaseballSeamMesh,
+            ));
+        });
 }
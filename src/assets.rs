@@ -0,0 +1,31 @@
+use crate::*;
+
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+
+/// loads `BaseballPluginConfig` from RON files with the `.aero.ron` extension
+#[derive(Debug, Default)]
+pub struct BaseballPluginConfigLoader;
+
+impl AssetLoader for BaseballPluginConfigLoader {
+    type Asset = BaseballPluginConfig;
+    type Settings = ();
+    type Error = BaseballFlightError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|e| BaseballFlightError::AssetLoadError(e.to_string()))?;
+        ron::de::from_bytes(&bytes).map_err(|e| BaseballFlightError::AssetLoadError(e.to_string()))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["aero.ron"]
+    }
+}
@@ -1,85 +1,233 @@
 use crate::*;
 
+use std::ops::{Add, Mul};
+
+// steps ending closer than this to the `T_STEP` grid (s) are snapped onto it
+const GRID_TOLERANCE: f64 = 1e-12;
+
+/// per frame conditions of the air the ball is flying through
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct FlightConditions {
+    // in kg/m^3
+    pub air_density: f64,
+    // in Pa·s
+    pub dynamic_viscosity: f64,
+    // wind velocity in the bevy world
+    pub wind: DVec3,
+    // gravitational acceleration in the bevy world
+    pub gravity: DVec3,
+}
+
+/// accelerations caused by each force, in the bevy world (m/s^2)
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ForceBreakdown {
+    pub drag: DVec3,
+    pub magnus: DVec3,
+    pub ssw: DVec3,
+    pub gravity: DVec3,
+}
+
+impl ForceBreakdown {
+    pub(crate) fn total(&self) -> DVec3 {
+        self.drag + self.magnus + self.ssw + self.gravity
+    }
+}
+
+impl Add for ForceBreakdown {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            drag: self.drag + rhs.drag,
+            magnus: self.magnus + rhs.magnus,
+            ssw: self.ssw + rhs.ssw,
+            gravity: self.gravity + rhs.gravity,
+        }
+    }
+}
+
+impl Mul<f64> for ForceBreakdown {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self {
+        Self {
+            drag: self.drag * rhs,
+            magnus: self.magnus * rhs,
+            ssw: self.ssw * rhs,
+            gravity: self.gravity * rhs,
+        }
+    }
+}
+
+// interpolated crossing of a strikezone panel, in field coordinates
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PlateCrossing {
+    pub panel: StrikezonePanel,
+    // time since release in s
+    pub time: f64,
+    pub position: DVec3,
+    // in the bevy world
+    pub world_position: DVec3,
+    pub velocity: DVec3,
+}
+
+impl PlateCrossing {
+    pub(crate) fn to_event(self, entity: Entity) -> PlateCrossedEvent {
+        let velocity = self.velocity.as_vec3();
+        PlateCrossedEvent {
+            entity,
+            panel: self.panel,
+            position: self.position.as_vec3(),
+            world_position: self.world_position.as_vec3(),
+            velocity,
+            time_of_flight: self.time as f32,
+            vertical_approach_angle: velocity.z.atan2(-velocity.y).to_degrees(),
+            horizontal_approach_angle: velocity.x.atan2(-velocity.y).to_degrees(),
+        }
+    }
+}
+
+/// flight state of the ball. the simulation is performed in the bevy world frame,
+/// `field_frame` is only used to orient the seams and to report field coordinates
 #[derive(Debug, Component, Clone, Default)]
 pub struct BaseballFlightState {
+    pub(crate) field_frame: FieldFrame,
     pub(crate) translation: DVec3,
     pub(crate) v: DVec3,
     pub(crate) spin: DVec3,
+    // in inches, relative to the center of the ball
     pub(crate) seams: Vec<DVec3>,
+    // rotation from the local frame of the ball (see `seam_curve`) into the bevy world
+    pub(crate) orientation: DQuat,
+    pub(crate) ball: BallProperties,
     pub(crate) time_elapsed: f64,
+    // time since the last point of the `T_STEP` grid, and the summed active seams found there
+    pub(crate) step_phase: f64,
+    pub(crate) ssw_seams: DVec3,
+    // latest accelerations per force, and the velocity and displacement each force has caused so far
+    pub(crate) accelerations: ForceBreakdown,
+    pub(crate) force_velocities: ForceBreakdown,
+    pub(crate) force_displacements: ForceBreakdown,
     //
     pub(crate) active: bool,
     // for recording purposes
     pub(crate) record_on: bool,
     pub(crate) record_times: Vec<f64>,
     pub(crate) record_positions: Vec<DVec3>,
-    // strikezone recording purposes (front, back)
+    // samples of every step since the last frame, drained by `TrajectoryRecorder`
+    pub(crate) record_steps: bool,
+    pub(crate) step_samples: Vec<TrajectorySample>,
+    // strikezone recording purposes (front, back) in field coordinates
     pub(crate) strikezone_panels_y: (f64, f64),
     pub(crate) pos_at_strikezone_panels_y: (DVec3, DVec3),
+    pub(crate) panels_crossed: (bool, bool),
+    // crossings since the last frame, drained into `PlateCrossedEvent`s
+    pub(crate) plate_crossings: Vec<PlateCrossing>,
 }
 
 impl BaseballFlightState {
+    /// positions at the strikezone panels (front, back) in the bevy world
     pub fn get_pos_at_strikezone_panels_z(&self) -> (Vec3, Vec3) {
         // record strikezone position
         let (pos_front, pos_back) = self.pos_at_strikezone_panels_y;
         (
-            pos_front.from_baseball_coord_to_bevy().as_vec3(),
-            pos_back.from_baseball_coord_to_bevy().as_vec3(),
+            self.field_frame.to_world_d(pos_front).as_vec3(),
+            self.field_frame.to_world_d(pos_back).as_vec3(),
         )
     }
 
+    /// positions at the strikezone panels (front, back) in field coordinates
+    pub fn get_field_pos_at_strikezone_panels(&self) -> (Vec3, Vec3) {
+        let (pos_front, pos_back) = self.pos_at_strikezone_panels_y;
+        (pos_front.as_vec3(), pos_back.as_vec3())
+    }
+
+    /// recorded positions in the bevy world
+    pub fn get_recorded_positions(&self) -> Vec<Vec3> {
+        self.record_positions
+            .iter()
+            .map(|position| position.as_vec3())
+            .collect()
+    }
+
+    /// recorded positions in field coordinates
+    pub fn get_recorded_field_positions(&self) -> Vec<Vec3> {
+        self.record_positions
+            .iter()
+            .map(|position| self.field_frame.to_field_d(*position).as_vec3())
+            .collect()
+    }
+
+    /// current spin in the bevy world (rad/s)
+    pub fn get_spin(&self) -> Vec3 {
+        self.spin.as_vec3()
+    }
+
+    /// rotation from the local frame of the ball into the bevy world.
+    /// the local frame is the one of `baseball_mesh` and `get_seam_points_local`
+    pub fn get_orientation(&self) -> Quat {
+        self.orientation.as_quat()
+    }
+
+    /// seam points in the local frame of the ball (m)
+    pub fn get_seam_points_local(&self) -> Vec<Vec3> {
+        seam_curve(N_SEAMS)
+            .iter()
+            .map(|point| (*point * self.ball.radius as f64).as_vec3())
+            .collect()
+    }
+
+    /// current seam points in the bevy world (m)
+    pub fn get_seam_points_world(&self) -> Vec<Vec3> {
+        self.seams
+            .iter()
+            .map(|point| (self.translation + *point / M_TO_INCHES as f64).as_vec3())
+            .collect()
+    }
+
     pub(crate) fn deactivate(&mut self) {
         self.active = false;
         self.time_elapsed = 0.;
+        self.record_steps = false;
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn from_params(
-        // position in baseball coord
+        // orientation of the field in the bevy world
+        field_frame: FieldFrame,
+        // position in the bevy world
         translation_: DVec3,
-        // velocity in baseball coord
+        // velocity in the bevy world
         velocity_: DVec3,
         // spin in rads
         spin_: DVec3,
-        // in rad
-        seam_y_angle_: f32,
-        // in rad
-        seam_z_angle_: f32,
+        seam_orientation: SeamOrientation,
         // other parameters...
         record_times_: Vec<f64>,
         //
         strikezone_panels_y: (f64, f64),
+        //
+        ball: BallProperties,
     ) -> Self {
         // Return owned value instead of reference
         let translation = translation_;
         let v = velocity_;
         let spin = spin_;
-        let seam_y_angle = seam_y_angle_ as f64;
-        let seam_z_angle = seam_z_angle_ as f64;
-
-        let seams = (0..N_SEAMS)
-            .map(|i| {
-                let alpha =
-                    (PI_64 * 2.) * (f64::from((i % N_SEAMS) as i16) / f64::from(N_SEAMS as i16));
-                let x = (1. / 13.) * (9. * f64::cos(alpha) - 4. * f64::cos(3. * alpha));
-                let y = (1. / 13.) * (9. * f64::sin(alpha) + 4. * f64::sin(3. * alpha));
-                let z = (12. / 13.) * f64::cos(2. * alpha);
-                DVec3::new(x, y, z) * (SEAM_DIAMETER / 2.)
-            })
-            .collect::<Vec<_>>();
-
-        let seams_adjsuted = seams
+        let (seam_y_angle, seam_z_angle) = seam_orientation.angles();
+        let (seam_y_angle, seam_z_angle) = (seam_y_angle as f64, seam_z_angle as f64);
+
+        // X axis of seams space should be the axis of rotation
+        // seam angles are defined in field coordinates
+        let field_spin = field_frame.direction_to_field_d(spin);
+        let orientation = field_frame.world_rotation_d()
+            * DQuat::from_rotation_arc(DVec3::X, field_spin.try_normalize().unwrap_or(DVec3::X))
+            * DQuat::from_rotation_z(-seam_z_angle)
+            * DQuat::from_rotation_y(seam_y_angle)
+            * DQuat::from_rotation_y(PI_64 / 2.)
+            * DQuat::from_rotation_x(-PI_64 / 2.);
+        let seams_adjsuted = seam_curve(N_SEAMS)
             .iter()
-            .map(|point| {
-                // X axis of seams space should be the axis of rotation
-                DQuat::from_rotation_arc(DVec3::X, spin.normalize()).mul_vec3(
-                    DQuat::from_rotation_z(-seam_z_angle).mul_vec3(
-                        DQuat::from_rotation_y(seam_y_angle).mul_vec3(
-                            DQuat::from_rotation_y(PI_64 / 2.)
-                                .mul_vec3(DQuat::from_rotation_x(-PI_64 / 2.).mul_vec3(*point)),
-                        ),
-                    ),
-                )
-            })
+            .map(|point| orientation.mul_vec3(*point * (ball.seam_diameter() / 2.)))
             .collect::<Vec<_>>();
         // info!("seams_adjsuted {:?}", seams_adjsuted);
 
@@ -90,17 +238,29 @@ impl BaseballFlightState {
 
         Self {
             // Return the value directly, not a reference
+            field_frame,
             translation,
             v,
             spin,
             seams: seams_adjsuted,
+            orientation,
+            ball,
             time_elapsed: 0.,
+            step_phase: 0.,
+            ssw_seams: DVec3::ZERO,
+            accelerations: ForceBreakdown::default(),
+            force_velocities: ForceBreakdown::default(),
+            force_displacements: ForceBreakdown::default(),
             active: true,
-            record_on: false,
+            record_on: !record_times_.is_empty(),
             record_times: record_times_,
             record_positions: default_record_positions,
+            record_steps: false,
+            step_samples: Vec::new(),
             strikezone_panels_y,
             pos_at_strikezone_panels_y: (DVec3::ZERO, DVec3::ZERO),
+            panels_crossed: (false, false),
+            plate_crossings: Vec::new(),
         }
     }
 
@@ -108,6 +268,8 @@ impl BaseballFlightState {
     pub(crate) fn update_state_and_get_acceleration(
         &mut self,
         config: &BaseballPluginConfig,
+        conditions: &FlightConditions,
+        integrator: &dyn Integrator,
         translation: DVec3,
         velocity: DVec3,
         delta_t: f64,
@@ -115,7 +277,7 @@ impl BaseballFlightState {
         self.translation = translation;
         self.v = velocity;
 
-        self.update_state(config, delta_t);
+        self.update_state(config, conditions, integrator, delta_t);
         let distance = self.translation - translation;
 
         (self.v * self.v - velocity * velocity) / (2. * distance)
@@ -125,65 +287,162 @@ impl BaseballFlightState {
     pub(crate) fn _update_state_and_get_velo(
         &mut self,
         config: &BaseballPluginConfig,
+        conditions: &FlightConditions,
+        integrator: &dyn Integrator,
         translation: DVec3,
         delta_t: f64,
     ) -> DVec3 {
         self.translation = translation;
 
-        self.update_state(config, delta_t);
+        self.update_state(config, conditions, integrator, delta_t);
 
         (self.translation - translation) / delta_t
     }
 
     // option 1
-    pub(crate) fn update_state(&mut self, config: &BaseballPluginConfig, delta_t: f64) {
-        let iterations = (delta_t * 1000.).floor() as usize;
+    // integrates in steps that end on a `T_STEP` grid starting at the release, and takes partial
+    // steps up to the frame boundaries, so the simulated time matches the physics time exactly
+    // and the flight does not depend on the frame rate
+    pub(crate) fn update_state(
+        &mut self,
+        config: &BaseballPluginConfig,
+        conditions: &FlightConditions,
+        integrator: &dyn Integrator,
+        delta_t: f64,
+    ) {
+        let mut remaining = delta_t;
+        while remaining > f64::EPSILON {
+            let dt = (T_STEP - self.step_phase).min(remaining);
+            self.step(config, conditions, integrator, dt);
+            remaining -= dt;
+        }
+    }
 
-        for _ in 0..iterations {
-            // rotate seams
-            self.seams = self
+    fn step(
+        &mut self,
+        config: &BaseballPluginConfig,
+        conditions: &FlightConditions,
+        integrator: &dyn Integrator,
+        dt: f64,
+    ) {
+        let wind = conditions.wind;
+
+        // the active seams are found on the grid, with the seams rotated to the end of the
+        // grid step, and held for the partial steps in between.
+        // low spin pitches use the knuckleball model instead of the active seams
+        if self.step_phase == 0. && !config.knuckleball.is_active(self.spin.length()) {
+            let rotation =
+                DQuat::from_axis_angle(self.spin.normalize_or_zero(), self.spin.length() * T_STEP);
+            let seams = self
                 .seams
                 .iter()
-                .map(|point| {
-                    // in seam space, the seams are rotating around the local x axis
-                    DQuat::from_axis_angle(self.spin.normalize(), self.spin.length() * T_STEP)
-                        .mul_vec3(*point)
-                })
+                .map(|point| rotation.mul_vec3(*point))
                 .collect::<Vec<_>>();
+            self.ssw_seams = self
+                .find_ssw_seams(&seams, &config.ssw, &(self.v - wind))
+                .into_iter()
+                .fold(DVec3::ZERO, |s_length, i| s_length + seams[i]);
+        } else if self.step_phase == 0. {
+            self.ssw_seams = DVec3::ZERO;
+        }
+        self.step_phase += dt;
+        if T_STEP - self.step_phase < GRID_TOLERANCE {
+            self.step_phase = 0.;
+        }
 
-            let active_seams = self.find_ssw_seams(&config.ssw);
-
-            let a = self.rk4(config, &active_seams);
-
-            self.time_elapsed += T_STEP;
+        // rotate seams
+        // in seam space, the seams are rotating around the local x axis
+        let rotation =
+            DQuat::from_axis_angle(self.spin.normalize_or_zero(), self.spin.length() * dt);
+        self.seams = self
+            .seams
+            .iter()
+            .map(|point| rotation.mul_vec3(*point))
+            .collect::<Vec<_>>();
+        self.orientation = (rotation * self.orientation).normalize();
 
-            self.v += DVec3::new(a.x, a.y, a.z - 32.2) * T_STEP;
-            self.translation += self.v * T_STEP;
+        // aerodynamic forces depend on the velocity relative to the air.
+        // seam orientation and active seams are held constant within a step
+        let acceleration = |_t: f64, state: &PointState| {
+            self.derivs(config, conditions, &(state.velocity - wind))
+                .total()
+        };
+        let next = integrator.integrate(
+            PointState {
+                position: self.translation,
+                velocity: self.v,
+            },
+            self.time_elapsed,
+            dt,
+            &acceleration,
+        );
+
+        // attribute the motion over the step to each force
+        let accelerations = self.derivs(config, conditions, &(self.v - wind));
+        self.accelerations = accelerations;
+        self.force_velocities = self.force_velocities + accelerations * dt;
+        self.force_displacements = self.force_displacements + self.force_velocities * dt;
+
+        self.time_elapsed += dt;
+
+        // spin decays over the step. the seams rotate with the decayed spin from the next step on
+        let decay_rate = config
+            .spin_decay
+            .decay_rate((self.v - wind).length(), self.ball.radius as f64);
+        self.spin *= (-decay_rate * dt).exp();
+
+        let (previous_translation, previous_velocity) = (self.translation, self.v);
+        self.v = next.velocity;
+        self.translation = next.position;
+
+        if self.record_steps {
+            self.step_samples.push(TrajectorySample::from_state(self));
+        }
 
-            // record position
-            if self.record_on {
-                if let Some(index) = self
-                    .record_times
-                    .iter()
-                    .position(|&t| t > self.time_elapsed)
-                {
-                    self.record_positions[index] = self.translation;
-                }
-            }
-            // record strikezone position
-            if (self.translation.y - self.strikezone_panels_y.0).abs() < 0.5 {
-                if (self.pos_at_strikezone_panels_y.0.y - self.strikezone_panels_y.0).abs()
-                    > (self.translation.y - self.strikezone_panels_y.0).abs()
-                {
-                    self.pos_at_strikezone_panels_y.0 = self.translation;
-                }
+        // record position
+        if self.record_on {
+            if let Some(index) = self
+                .record_times
+                .iter()
+                .position(|&t| t > self.time_elapsed)
+            {
+                self.record_positions[index] = self.translation;
             }
-            if (self.translation.y - self.strikezone_panels_y.1).abs() < 0.5 {
-                if (self.pos_at_strikezone_panels_y.1.y - self.strikezone_panels_y.1).abs()
-                    > (self.translation.y - self.strikezone_panels_y.1).abs()
-                {
-                    self.pos_at_strikezone_panels_y.1 = self.translation;
-                }
+        }
+        // record strikezone crossings, interpolated within the step
+        let (position_before, velocity_before) = (
+            self.field_frame.to_field_d(previous_translation),
+            self.field_frame.direction_to_field_d(previous_velocity),
+        );
+        let (position_after, velocity_after) = (
+            self.field_frame.to_field_d(self.translation),
+            self.field_frame.direction_to_field_d(self.v),
+        );
+        for panel in [StrikezonePanel::Front, StrikezonePanel::Back] {
+            let (panel_y, crossed, position) = match panel {
+                StrikezonePanel::Front => (
+                    self.strikezone_panels_y.0,
+                    &mut self.panels_crossed.0,
+                    &mut self.pos_at_strikezone_panels_y.0,
+                ),
+                StrikezonePanel::Back => (
+                    self.strikezone_panels_y.1,
+                    &mut self.panels_crossed.1,
+                    &mut self.pos_at_strikezone_panels_y.1,
+                ),
+            };
+            if !*crossed && position_before.y >= panel_y && position_after.y < panel_y {
+                let fraction =
+                    (position_before.y - panel_y) / (position_before.y - position_after.y);
+                *crossed = true;
+                *position = position_before.lerp(position_after, fraction);
+                self.plate_crossings.push(PlateCrossing {
+                    panel,
+                    time: self.time_elapsed - dt * (1. - fraction),
+                    position: *position,
+                    world_position: previous_translation.lerp(self.translation, fraction),
+                    velocity: velocity_before.lerp(velocity_after, fraction),
+                });
             }
         }
     }
@@ -191,19 +450,19 @@ impl BaseballFlightState {
     // find seam indices that affect ssw
     // note that the local x-axis of the seams is the rotational axis
     // we need to calculate the velocity vector in relation to the seams' local bases
-    fn find_ssw_seams(&self, ssw: &SeamShiftedWake) -> Vec<usize> {
+    // the activation region is defined in field coordinates
+    fn find_ssw_seams(&self, seams: &[DVec3], ssw: &SeamShiftedWake, v_air: &DVec3) -> Vec<usize> {
         // let v_adjusted = self.in_seam_space(self.v);
-        let rot_v = DQuat::from_rotation_arc(-DVec3::Y, self.v.normalize());
+        let v_field = self.field_frame.direction_to_field_d(*v_air);
+        let rot_v = DQuat::from_rotation_arc(-DVec3::Y, v_field.normalize());
         let rot_spin = DQuat::from_rotation_x(ssw.seam_shift_factor * T_STEP);
-        let (max, min) = ssw.get_activation_region();
+        let (max, min) = ssw.get_activation_region(self.ball.seam_diameter());
 
         (0..N_SEAMS)
             .filter(|&i| {
-                let point_adjusted = rot_v.mul_vec3(
-                    rot_v
-                        .inverse()
-                        .mul_vec3(rot_spin.inverse().mul_vec3(self.seams[i])),
-                );
+                let seam = self.field_frame.direction_to_field_d(seams[i]);
+                let point_adjusted =
+                    rot_v.mul_vec3(rot_v.inverse().mul_vec3(rot_spin.inverse().mul_vec3(seam)));
                 if (point_adjusted.x < max.x)
                     && (point_adjusted.x > min.x)
                     // within y range
@@ -213,7 +472,7 @@ impl BaseballFlightState {
                     && (point_adjusted.z < max.z)
                     && (point_adjusted.z > min.z)
                 {
-                    self.outside_separated_flow(ssw, i)
+                    Self::outside_separated_flow(seams, ssw, i, v_air)
                 } else {
                     false
                 }
@@ -223,11 +482,16 @@ impl BaseballFlightState {
 
     /// since seams in the activation region cannot cause a separated flow to
     /// become separated again this function will eliminate any inline seams
-    fn outside_separated_flow(&self, ssw: &SeamShiftedWake, index: usize) -> bool {
-        let point = &self.seams[index];
-        let next_point = &self.seams[(index + 1) % N_SEAMS];
-        let prev_point = &self.seams[(index + N_SEAMS - 1) % N_SEAMS];
-        let normalized_v: &DVec3 = &self.v.normalize();
+    fn outside_separated_flow(
+        seams: &[DVec3],
+        ssw: &SeamShiftedWake,
+        index: usize,
+        v_air: &DVec3,
+    ) -> bool {
+        let point = &seams[index];
+        let next_point = &seams[(index + 1) % N_SEAMS];
+        let prev_point = &seams[(index + N_SEAMS - 1) % N_SEAMS];
+        let normalized_v: &DVec3 = &v_air.normalize();
 
         let angle_d = normalized_v.dot((*point - *prev_point).normalize()).acos();
         let angle_u = normalized_v.dot((*next_point - *point).normalize()).acos();
@@ -236,86 +500,247 @@ impl BaseballFlightState {
             && (angle_u - PI_64).abs() >= ssw.separated_flow_range
     }
 
-    fn rk4(&self, config: &BaseballPluginConfig, active_seams: &Vec<usize>) -> DVec3 {
-        let spin = &self.spin;
-        let seams = &self.seams;
-        let time_elapsed = self.time_elapsed as f64;
-
-        let v_1 = self.v;
-        let t_1 = time_elapsed;
-        let a_1 = Self::derivs(config, &v_1, spin, seams, t_1, active_seams);
-
-        let v_2 = v_1 + a_1 * T_STEP * 0.5;
-        let t_2 = t_1 + T_STEP * 0.5;
-        let a_2 = Self::derivs(config, &v_2, spin, seams, t_2, active_seams);
-
-        let v_3 = v_2 + a_2 * T_STEP * 0.5;
-        let t_3 = t_2 + T_STEP * 0.5;
-        let a_3 = Self::derivs(config, &v_3, spin, seams, t_3, active_seams);
-
-        let v_4 = v_3 + a_3 * T_STEP;
-        let t_4 = t_3 + T_STEP;
-        let a_4 = Self::derivs(config, &v_4, spin, seams, t_4, active_seams);
-
-        let slope = (a_1 + 2. * (a_2 + a_3) + a_4) / 6.0;
-
-        // if self.time_elapsed > 0. && self.time_elapsed < 0.012 {
-        //     info!("a_1 {:?}", a_1);
-        //     info!("a_2 {:?}", a_2);
-        //     info!("a_3 {:?}", a_3);
-        //     info!("a_4 {:?}", a_4);
-        // }
-
-        slope
+    /// lateral force coefficient times its direction for the current seam orientation.
+    /// the seam angle is measured between `v` and the local x axis of the seam curve
+    fn knuckleball_acceleration(&self, knuckleball: &KnuckleballModel, v: &DVec3) -> DVec3 {
+        let v_normalized = v.normalize_or_zero();
+        let axis = self.orientation * DVec3::X;
+        let seam_angle = axis.dot(v_normalized).clamp(-1., 1.).acos();
+        // the force pushes towards the seam axis, perpendicular to the flow
+        let direction = (axis - v_normalized * axis.dot(v_normalized)).normalize_or_zero();
+        direction * knuckleball.lateral_coefficient(seam_angle)
     }
 
+    // `v` is the velocity relative to the air
     fn derivs(
+        &self,
         config: &BaseballPluginConfig,
+        conditions: &FlightConditions,
         v: &DVec3,
-        spin: &DVec3,
-        seams: &Vec<DVec3>,
-        time_elapsed: f64,
-        active_seams: &Vec<usize>,
-    ) -> DVec3 {
+    ) -> ForceBreakdown {
+        let spin = &self.spin;
         let v_tot = v.length();
         let spin_rate = spin.length();
-
-        let rw = (DIAMETER / 2.) * spin_rate;
-        let s = (rw / v_tot) * (-time_elapsed / SPIN_DECAY).exp();
-        let cl = 1. / (2.42 + (0.4 / s));
+        let c_0 = self.ball.c_0(conditions.air_density);
+
+        let rw = self.ball.radius as f64 * spin_rate;
+        let flow = FlowConditions {
+            speed: v_tot,
+            reynolds_number: conditions.air_density * v_tot * 2. * self.ball.radius as f64
+                / conditions.dynamic_viscosity,
+            spin_rate,
+            spin_factor: rw / v_tot,
+        };
 
         // drag force
         let a_drag = if config.drag_on {
-            *v * -C_0 * CD_CONST * v_tot
+            let cd = config.drag_model.drag_coefficient(&flow);
+            *v * -c_0 * cd * v_tot
         } else {
             DVec3::ZERO
         };
 
         // magnus force
-        let a_spin = if config.magnus_on {
+        let a_spin = if config.magnus_on && spin_rate > 0. {
+            let cl = config.lift_model.lift_coefficient(&flow);
             let [u, v, w] = v.to_array();
             let [spin_x, spin_y, spin_z] = spin.to_array();
             DVec3::new(
                 spin_y * w - spin_z * v,
                 spin_z * u - spin_x * w,
                 spin_x * v - spin_y * u,
-            ) * C_0
+            ) * c_0
                 * (cl / spin_rate)
                 * v_tot
         } else {
             DVec3::ZERO
         };
 
-        // ssw
-        let a_ssw = if config.ssw_on {
-            let seams_length = active_seams
-                .iter()
-                .fold(DVec3::ZERO, |s_length, &i| s_length + seams[i]);
-            seams_length * -C_0 * C_SEAMS * v_tot.powi(2)
+        // ssw. for low spin pitches the seam force follows the instantaneous seam angle
+        let a_ssw = if config.ssw_on && config.knuckleball.is_active(spin_rate) {
+            self.knuckleball_acceleration(&config.knuckleball, v) * c_0 * v_tot.powi(2)
+        } else if config.ssw_on {
+            self.ssw_seams * -c_0 * config.ssw.seam_coefficient * v_tot.powi(2)
         } else {
             DVec3::ZERO
         };
 
-        a_drag + a_spin + a_ssw
+        ForceBreakdown {
+            drag: a_drag,
+            magnus: a_spin,
+            ssw: a_ssw,
+            gravity: conditions.gravity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fastball() -> BaseballFlightState {
+        pitch(FieldFrame::default(), Vec3::NEG_Y)
+    }
+
+    // 96 mph backspin fastball released in `direction` (field coordinates)
+    fn pitch(field_frame: FieldFrame, direction: Vec3) -> BaseballFlightState {
+        let spin =
+            get_angular_velocity_from_parameters(Tilt::default(), 1., 2400., GyroPole::default());
+        BaseballFlightState::from_params(
+            field_frame,
+            field_frame
+                .to_world(Vec3::new(-0.48, 16.764, 1.82))
+                .as_dvec3(),
+            field_frame
+                .direction_to_world(direction.normalize() * 96. * MPH_TO_MS)
+                .as_dvec3(),
+            field_frame.direction_to_world(spin).as_dvec3(),
+            SeamOrientation::FourSeam,
+            vec![],
+            (PLATE_FRONT as f64, 0.),
+            BallProperties::default(),
+        )
+    }
+
+    // 65 mph knuckleball with `spin_rate` rpm of backspin
+    fn knuckleball(spin_rate: f32) -> BaseballFlightState {
+        let field_frame = FieldFrame::default();
+        let spin = get_angular_velocity_from_parameters(
+            Tilt::default(),
+            1.,
+            spin_rate,
+            GyroPole::default(),
+        );
+        BaseballFlightState::from_params(
+            field_frame,
+            field_frame
+                .to_world(Vec3::new(-0.48, 16.764, 1.82))
+                .as_dvec3(),
+            field_frame
+                .direction_to_world(Vec3::NEG_Y * 65. * MPH_TO_MS)
+                .as_dvec3(),
+            field_frame.direction_to_world(spin).as_dvec3(),
+            SeamOrientation::Knuckleball,
+            vec![],
+            (PLATE_FRONT as f64, 0.),
+            BallProperties::default(),
+        )
+    }
+
+    // field x acceleration of the seams every frame until the ball reaches home plate
+    fn lateral_accelerations(
+        config: &BaseballPluginConfig,
+        mut state: BaseballFlightState,
+    ) -> Vec<f64> {
+        let mut accelerations = vec![];
+        while !state.panels_crossed.1 && state.time_elapsed < 2. {
+            state.update_state(config, &conditions(), &config.integrator, 1. / 60.);
+            accelerations.push(
+                state
+                    .field_frame
+                    .direction_to_field_d(state.accelerations.ssw)
+                    .x,
+            );
+        }
+        accelerations
+    }
+
+    fn conditions() -> FlightConditions {
+        FlightConditions {
+            air_density: Atmosphere::default().air_density() as f64,
+            dynamic_viscosity: Atmosphere::default().dynamic_viscosity() as f64,
+            wind: DVec3::ZERO,
+            gravity: DVec3::NEG_Y * 9.81,
+        }
+    }
+
+    #[test]
+    fn flight_is_independent_of_the_frame_rate() {
+        let config = BaseballPluginConfig::default();
+        let conditions = conditions();
+        // 0.5 s of flight at 30, 60 and 144 fps
+        let states = [(30, 15), (60, 30), (144, 72)].map(|(fps, frames)| {
+            let mut state = fastball();
+            for _ in 0..frames {
+                state.update_state(&config, &conditions, &config.integrator, 1. / fps as f64);
+            }
+            state
+        });
+
+        for state in &states[1..] {
+            assert!((state.time_elapsed - states[0].time_elapsed).abs() < 1e-9);
+            assert!(
+                state.translation.distance(states[0].translation) < 1e-6,
+                "{} and {}",
+                state.translation,
+                states[0].translation
+            );
+        }
+    }
+
+    #[test]
+    fn plate_crossing_is_interpolated_to_the_panel() {
+        let config = BaseballPluginConfig::default();
+        let field_frame = FieldFrame {
+            home_plate: Vec3::new(3., 0., -2.),
+            rotation: Quat::from_rotation_y(0.4),
+        };
+        // moving towards field +x
+        let mut state = pitch(field_frame, Vec3::new(0.05, -1., 0.));
+        let frame = 1. / 60.;
+        let mut crossings = vec![];
+        while crossings.len() < 2 && state.time_elapsed < 1. {
+            let previous = field_frame.to_field_d(state.translation);
+            state.update_state(&config, &conditions(), &config.integrator, frame);
+            for crossing in state.plate_crossings.drain(..) {
+                let current = field_frame.to_field_d(state.translation);
+                // within the frame it was detected in, and on the plane of the panel
+                assert!(crossing.time <= state.time_elapsed);
+                assert!(crossing.time > state.time_elapsed - frame);
+                assert!(crossing.position.z < previous.z && crossing.position.z > current.z);
+                crossings.push(crossing.to_event(Entity::PLACEHOLDER));
+            }
+        }
+
+        assert_eq!(crossings.len(), 2);
+        let (front, back) = (&crossings[0], &crossings[1]);
+        assert_eq!(front.panel, StrikezonePanel::Front);
+        assert_eq!(back.panel, StrikezonePanel::Back);
+        assert!((front.position.y - PLATE_FRONT).abs() < 1e-4);
+        assert!(back.position.y.abs() < 1e-4);
+        assert!(front.time_of_flight < back.time_of_flight);
+        for crossing in &crossings {
+            assert!(
+                crossing
+                    .world_position
+                    .distance(field_frame.to_world(crossing.position))
+                    < 1e-4
+            );
+            // descending and moving towards field +x
+            assert!(crossing.vertical_approach_angle < 0.);
+            assert!(crossing.horizontal_approach_angle > 0.);
+        }
+    }
+
+    #[test]
+    fn knuckleball_force_changes_sign_over_the_flight() {
+        let config = BaseballPluginConfig::default();
+        let accelerations = lateral_accelerations(&config, knuckleball(60.));
+        assert!(accelerations.iter().any(|a| *a > 0.1), "{accelerations:?}");
+        assert!(accelerations.iter().any(|a| *a < -0.1), "{accelerations:?}");
+    }
+
+    #[test]
+    fn knuckleball_mode_is_bypassed_when_disabled() {
+        let mut config = BaseballPluginConfig::default();
+        let enabled = lateral_accelerations(&config, knuckleball(60.));
+        config.knuckleball.enabled = false;
+        let disabled = lateral_accelerations(&config, knuckleball(60.));
+        // without the mode the seam shifted wake applies, as it does above the threshold
+        config.knuckleball.enabled = true;
+        config.knuckleball.spin_threshold = 0.;
+        let above_threshold = lateral_accelerations(&config, knuckleball(60.));
+        assert_eq!(disabled, above_threshold);
+        assert_ne!(disabled, enabled);
     }
 }
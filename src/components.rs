@@ -1,25 +1,248 @@
 use crate::*;
 
+/// physical properties of the ball.
+/// the collider, rapier mass and aerodynamic coefficients are all derived from these
+#[derive(Debug, Clone, Copy, Component, Reflect, PartialEq)]
+#[reflect(Component)]
+pub struct BallProperties {
+    // in kg
+    pub mass: f32,
+    // in m
+    pub radius: f32,
+}
+
+impl Default for BallProperties {
+    fn default() -> Self {
+        Self::mlb()
+    }
+}
+
+impl BallProperties {
+    /// official baseball (5 1/8 oz, 2 15/16 in diameter)
+    pub fn mlb() -> Self {
+        Self {
+            mass: MASS,
+            radius: RADIUS,
+        }
+    }
+
+    /// 12 in fastpitch softball (6.75 oz, 12 in circumference)
+    pub fn softball() -> Self {
+        Self {
+            mass: 0.191,
+            radius: 0.0485,
+        }
+    }
+
+    /// e.g. weighted training balls. mass in kg
+    pub fn with_mass(self, mass: f32) -> Self {
+        Self { mass, ..self }
+    }
+
+    /// radius in m
+    pub fn with_radius(self, radius: f32) -> Self {
+        Self { radius, ..self }
+    }
+
+    /// seam geometry is in inches
+    pub(crate) fn seam_diameter(&self) -> f64 {
+        (self.radius * M_TO_INCHES) as f64 * 2.
+    }
+
+    /// aerodynamic constant `0.5 * rho * A / m`.
+    /// `air_density` is in kg/m^3
+    pub(crate) fn c_0(&self, air_density: f64) -> f64 {
+        let area = PI_64 * (self.radius as f64).powi(2);
+        0.5 * air_density * area / self.mass as f64
+    }
+}
+
+/// overrides the plugin's integrator for a single ball
+#[derive(Debug, Clone, Copy, Component, Reflect)]
+#[reflect(Component)]
+pub struct FlightIntegrator(pub IntegratorKind);
+
+/// overrides the plugin's force toggles and coefficient models for a single ball.
+/// `None` falls back to `BaseballPluginConfig`. seam shifted wake parameters are
+/// overridden with the `SeamShiftedWake` component
+#[derive(Debug, Clone, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct AeroOverrides {
+    pub ssw_on: Option<bool>,
+    pub magnus_on: Option<bool>,
+    pub drag_on: Option<bool>,
+    pub drag_model: Option<DragModelKind>,
+    pub lift_model: Option<LiftModelKind>,
+    pub spin_decay: Option<SpinDecayModel>,
+    pub knuckleball: Option<KnuckleballModel>,
+}
+
+impl AeroOverrides {
+    pub fn with_ssw(self, ssw_on: bool) -> Self {
+        Self {
+            ssw_on: Some(ssw_on),
+            ..self
+        }
+    }
+
+    pub fn with_magnus(self, magnus_on: bool) -> Self {
+        Self {
+            magnus_on: Some(magnus_on),
+            ..self
+        }
+    }
+
+    pub fn with_drag(self, drag_on: bool) -> Self {
+        Self {
+            drag_on: Some(drag_on),
+            ..self
+        }
+    }
+
+    pub fn with_drag_model(self, drag_model: DragModelKind) -> Self {
+        Self {
+            drag_model: Some(drag_model),
+            ..self
+        }
+    }
+
+    pub fn with_lift_model(self, lift_model: LiftModelKind) -> Self {
+        Self {
+            lift_model: Some(lift_model),
+            ..self
+        }
+    }
+
+    pub fn with_spin_decay(self, spin_decay: SpinDecayModel) -> Self {
+        Self {
+            spin_decay: Some(spin_decay),
+            ..self
+        }
+    }
+
+    pub fn with_knuckleball(self, knuckleball: KnuckleballModel) -> Self {
+        Self {
+            knuckleball: Some(knuckleball),
+            ..self
+        }
+    }
+
+    pub(crate) fn apply(&self, config: &mut BaseballPluginConfig) {
+        if let Some(ssw_on) = self.ssw_on {
+            config.ssw_on = ssw_on;
+        }
+        if let Some(magnus_on) = self.magnus_on {
+            config.magnus_on = magnus_on;
+        }
+        if let Some(drag_on) = self.drag_on {
+            config.drag_on = drag_on;
+        }
+        if let Some(drag_model) = &self.drag_model {
+            config.drag_model = drag_model.clone();
+        }
+        if let Some(lift_model) = &self.lift_model {
+            config.lift_model = lift_model.clone();
+        }
+        if let Some(spin_decay) = self.spin_decay {
+            config.spin_decay = spin_decay;
+        }
+        if let Some(knuckleball) = &self.knuckleball {
+            config.knuckleball = knuckleball.clone();
+        }
+    }
+}
+
+/// drag, magnus, ssw and gravity vectors
+#[derive(Debug, Clone, Copy, Default, Reflect, PartialEq)]
+pub struct ForceComponents {
+    pub drag: Vec3,
+    pub magnus: Vec3,
+    pub ssw: Vec3,
+    pub gravity: Vec3,
+}
+
+impl ForceComponents {
+    pub fn total(&self) -> Vec3 {
+        self.drag + self.magnus + self.ssw + self.gravity
+    }
+
+    pub(crate) fn from_breakdown(breakdown: &ForceBreakdown, scale: f64) -> Self {
+        Self {
+            drag: (breakdown.drag * scale).as_vec3(),
+            magnus: (breakdown.magnus * scale).as_vec3(),
+            ssw: (breakdown.ssw * scale).as_vec3(),
+            gravity: (breakdown.gravity * scale).as_vec3(),
+        }
+    }
+
+    pub(crate) fn map(&self, f: impl Fn(Vec3) -> Vec3) -> Self {
+        Self {
+            drag: f(self.drag),
+            magnus: f(self.magnus),
+            ssw: f(self.ssw),
+            gravity: f(self.gravity),
+        }
+    }
+}
+
+/// breakdown of the forces acting on the ball, updated every frame while aerodynamics are active
+#[derive(Debug, Clone, Copy, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct AeroForces {
+    // in N, in the bevy world
+    pub forces: ForceComponents,
+    // in N, in field coordinates
+    pub field_forces: ForceComponents,
+    // displacement caused by each force since activation in m, in the bevy world
+    pub displacements: ForceComponents,
+    // displacement caused by each force since activation in m, in field coordinates
+    pub field_displacements: ForceComponents,
+}
+
+impl AeroForces {
+    pub(crate) fn from_state(state: &BaseballFlightState) -> Self {
+        let forces = ForceComponents::from_breakdown(&state.accelerations, state.ball.mass as f64);
+        let displacements = ForceComponents::from_breakdown(&state.force_displacements, 1.);
+        let to_field = |vec| state.field_frame.direction_to_field(vec);
+        Self {
+            forces,
+            field_forces: forces.map(to_field),
+            displacements,
+            field_displacements: displacements.map(to_field),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Bundle)]
 pub struct BaseballFlightBundle {
     pub state: BaseballFlightState,
+    pub properties: BallProperties,
     pub collider: Collider,
     pub rigid_body: RigidBody,
     pub mass: ColliderMassProperties,
     pub gravity_scale: GravityScale,
+    pub forces: AeroForces,
     // pub transform: Transform,
     // pub linear_velocity: LinearVelocity,
 }
 
 impl Default for BaseballFlightBundle {
     fn default() -> Self {
-        let collider = Collider::ball(RADIUS);
+        Self::from_properties(BallProperties::default())
+    }
+}
+
+impl BaseballFlightBundle {
+    pub fn from_properties(properties: BallProperties) -> Self {
+        let collider = Collider::ball(properties.radius);
         Self {
             state: BaseballFlightState::default(),
+            properties,
             collider,
             rigid_body: RigidBody::Dynamic,
-            mass: ColliderMassProperties::Mass(MASS),
+            mass: ColliderMassProperties::Mass(properties.mass),
             gravity_scale: GravityScale(0.0),
+            forces: AeroForces::default(),
             // transform: Transform::default(),
             // linear_velocity: LinearVelocity::default(),
         }
@@ -13,6 +13,12 @@ pub enum BaseballFlightError {
     SimulationError,
 
     UnexpectedError,
+
+    AssetLoadError(String),
+
+    ExportError(String),
+
+    ImportError(String),
 }
 
 pub(crate) type Result<T> = std::result::Result<T, BaseballFlightError>;
@@ -32,6 +38,11 @@ impl fmt::Display for BaseballFlightError {
             BaseballFlightError::UnexpectedError => {
                 write!(f, "Unexpected error occurred")
             }
+            BaseballFlightError::AssetLoadError(msg) => write!(f, "Asset load error: {}", msg),
+            BaseballFlightError::ExportError(msg) => write!(f, "Export error: {}", msg),
+            BaseballFlightError::ImportError(msg) => write!(f, "Import error: {}", msg),
         }
     }
 }
+
+impl std::error::Error for BaseballFlightError {}
@@ -3,11 +3,10 @@ use crate::*;
 #[derive(Debug, Clone, Event)]
 pub struct ActivateAerodynamicsEvent {
     pub entity: Entity,
-    pub seam_y_angle: f32,
-    pub seam_z_angle: f32,
+    pub seam_orientation: SeamOrientation,
     //
     pub record_times: Vec<f64>,
-    //
+    // front and back panels of the strikezone as distances from home plate along the field's y axis in m
     pub strikezone_panels_z: (f32, f32),
 }
 
@@ -16,3 +15,38 @@ pub struct PostActivateAerodynamicsEvent(pub Entity);
 
 #[derive(Debug, Clone, Copy, Event)]
 pub struct DisableAerodynamicsEvent(pub Entity);
+
+/// sent by `TrajectoryRecorder` with the recorded flight once the ball is deactivated
+#[derive(Debug, Clone, Event)]
+pub struct TrajectoryRecordedEvent {
+    pub entity: Entity,
+    pub trajectory: Trajectory,
+}
+
+/// front or back panel of the strikezone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum StrikezonePanel {
+    Front,
+    Back,
+}
+
+/// sent once per panel and ball when the ball crosses a strikezone panel.
+/// positions and velocities are interpolated to the plane of the panel and are in field coordinates,
+/// except for `world_position`
+#[derive(Debug, Clone, Copy, Event)]
+pub struct PlateCrossedEvent {
+    pub entity: Entity,
+    pub panel: StrikezonePanel,
+    // in m
+    pub position: Vec3,
+    // the same point in the bevy world, in m
+    pub world_position: Vec3,
+    // in m/s
+    pub velocity: Vec3,
+    // time since release in s
+    pub time_of_flight: f32,
+    // in degrees. negative when descending
+    pub vertical_approach_angle: f32,
+    // in degrees. positive when moving towards the catcher's right (field +x)
+    pub horizontal_approach_angle: f32,
+}
@@ -0,0 +1,349 @@
+use crate::*;
+
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Mul};
+
+/// translational state of the ball
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PointState {
+    pub position: DVec3,
+    pub velocity: DVec3,
+}
+
+impl Add for PointState {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            position: self.position + rhs.position,
+            velocity: self.velocity + rhs.velocity,
+        }
+    }
+}
+
+impl Mul<f64> for PointState {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self {
+        Self {
+            position: self.position * rhs,
+            velocity: self.velocity * rhs,
+        }
+    }
+}
+
+/// total acceleration of the ball at time `t` for a given state
+pub type AccelerationFn<'a> = dyn Fn(f64, &PointState) -> DVec3 + 'a;
+
+/// advances the translational state of the ball over a single time step
+pub trait Integrator {
+    fn integrate(
+        &self,
+        state: PointState,
+        t: f64,
+        dt: f64,
+        acceleration: &AccelerationFn,
+    ) -> PointState;
+}
+
+// time derivative of the state
+fn derivative(t: f64, state: &PointState, acceleration: &AccelerationFn) -> PointState {
+    PointState {
+        position: state.velocity,
+        velocity: acceleration(t, state),
+    }
+}
+
+/// semi-implicit euler
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Euler;
+
+impl Integrator for Euler {
+    fn integrate(
+        &self,
+        state: PointState,
+        t: f64,
+        dt: f64,
+        acceleration: &AccelerationFn,
+    ) -> PointState {
+        let velocity = state.velocity + acceleration(t, &state) * dt;
+        PointState {
+            position: state.position + velocity * dt,
+            velocity,
+        }
+    }
+}
+
+/// classic fourth order runge-kutta on position and velocity
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rk4;
+
+impl Integrator for Rk4 {
+    fn integrate(
+        &self,
+        state: PointState,
+        t: f64,
+        dt: f64,
+        acceleration: &AccelerationFn,
+    ) -> PointState {
+        let k_1 = derivative(t, &state, acceleration);
+        let k_2 = derivative(t + dt * 0.5, &(state + k_1 * (dt * 0.5)), acceleration);
+        let k_3 = derivative(t + dt * 0.5, &(state + k_2 * (dt * 0.5)), acceleration);
+        let k_4 = derivative(t + dt, &(state + k_3 * dt), acceleration);
+
+        state + (k_1 + (k_2 + k_3) * 2. + k_4) * (dt / 6.)
+    }
+}
+
+/// velocity verlet. symplectic for position dependent forces
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Verlet;
+
+impl Integrator for Verlet {
+    fn integrate(
+        &self,
+        state: PointState,
+        t: f64,
+        dt: f64,
+        acceleration: &AccelerationFn,
+    ) -> PointState {
+        let a_0 = acceleration(t, &state);
+        let half_step = PointState {
+            position: state.position + state.velocity * dt + a_0 * (0.5 * dt * dt),
+            velocity: state.velocity + a_0 * (0.5 * dt),
+        };
+        let a_1 = acceleration(t + dt, &half_step);
+        PointState {
+            position: half_step.position,
+            velocity: half_step.velocity + a_1 * (0.5 * dt),
+        }
+    }
+}
+
+/// adaptive dormand-prince 5(4). the step is subdivided until the local error
+/// estimate is within `tolerance`
+#[derive(Debug, Clone, Copy)]
+pub struct DormandPrince {
+    // maximum local error of position (m) and velocity (m/s)
+    pub tolerance: f64,
+    // in s. steps of this size are accepted whatever their error, so the step can not shrink to zero.
+    // values that are not positive use the default
+    pub min_step: f64,
+}
+
+impl Default for DormandPrince {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-9,
+            min_step: 1e-6,
+        }
+    }
+}
+
+impl DormandPrince {
+    const C: [f64; 7] = [0., 1. / 5., 3. / 10., 4. / 5., 8. / 9., 1., 1.];
+    const A: [[f64; 6]; 7] = [
+        [0., 0., 0., 0., 0., 0.],
+        [1. / 5., 0., 0., 0., 0., 0.],
+        [3. / 40., 9. / 40., 0., 0., 0., 0.],
+        [44. / 45., -56. / 15., 32. / 9., 0., 0., 0.],
+        [
+            19372. / 6561.,
+            -25360. / 2187.,
+            64448. / 6561.,
+            -212. / 729.,
+            0.,
+            0.,
+        ],
+        [
+            9017. / 3168.,
+            -355. / 33.,
+            46732. / 5247.,
+            49. / 176.,
+            -5103. / 18656.,
+            0.,
+        ],
+        [
+            35. / 384.,
+            0.,
+            500. / 1113.,
+            125. / 192.,
+            -2187. / 6784.,
+            11. / 84.,
+        ],
+    ];
+    // fifth order weights
+    const B_5: [f64; 7] = [
+        35. / 384.,
+        0.,
+        500. / 1113.,
+        125. / 192.,
+        -2187. / 6784.,
+        11. / 84.,
+        0.,
+    ];
+    // fourth order weights
+    const B_4: [f64; 7] = [
+        5179. / 57600.,
+        0.,
+        7571. / 16695.,
+        393. / 640.,
+        -92097. / 339200.,
+        187. / 2100.,
+        1. / 40.,
+    ];
+
+    // returns the fifth order solution and the local error estimate
+    fn try_step(
+        state: &PointState,
+        t: f64,
+        h: f64,
+        acceleration: &AccelerationFn,
+    ) -> (PointState, f64) {
+        let mut k = [PointState::default(); 7];
+        for i in 0..7 {
+            let stage = (0..i).fold(*state, |stage, j| stage + k[j] * (h * Self::A[i][j]));
+            k[i] = derivative(t + Self::C[i] * h, &stage, acceleration);
+        }
+
+        let (y_5, y_4) = (0..7).fold((*state, *state), |(y_5, y_4), i| {
+            (
+                y_5 + k[i] * (h * Self::B_5[i]),
+                y_4 + k[i] * (h * Self::B_4[i]),
+            )
+        });
+        let error = (y_5.position - y_4.position)
+            .abs()
+            .max_element()
+            .max((y_5.velocity - y_4.velocity).abs().max_element());
+
+        (y_5, error)
+    }
+}
+
+impl Integrator for DormandPrince {
+    fn integrate(
+        &self,
+        state: PointState,
+        t: f64,
+        dt: f64,
+        acceleration: &AccelerationFn,
+    ) -> PointState {
+        let min_step = if self.min_step > 0. {
+            self.min_step
+        } else {
+            Self::default().min_step
+        };
+        let mut state = state;
+        let mut elapsed = 0.;
+        let mut h = dt;
+
+        while dt - elapsed > f64::EPSILON {
+            h = h.min(dt - elapsed);
+            let (next, error) = Self::try_step(&state, t + elapsed, h, acceleration);
+
+            if error <= self.tolerance || h <= min_step {
+                state = next;
+                elapsed += h;
+            }
+
+            // grow or shrink the next step based on the error estimate
+            let factor = if error == 0. {
+                5.
+            } else {
+                (0.9 * (self.tolerance / error).powf(0.2)).clamp(0.2, 5.)
+            };
+            h = (h * factor).max(min_step);
+        }
+
+        state
+    }
+}
+
+/// integrator selection for the plugin or for a single ball (see `FlightIntegrator`)
+#[derive(Debug, Clone, Copy, Default, Reflect, Serialize, Deserialize)]
+pub enum IntegratorKind {
+    Euler,
+    #[default]
+    Rk4,
+    DormandPrince {
+        tolerance: f64,
+        min_step: f64,
+    },
+    Verlet,
+}
+
+impl Integrator for IntegratorKind {
+    fn integrate(
+        &self,
+        state: PointState,
+        t: f64,
+        dt: f64,
+        acceleration: &AccelerationFn,
+    ) -> PointState {
+        match *self {
+            Self::Euler => Euler.integrate(state, t, dt, acceleration),
+            Self::Rk4 => Rk4.integrate(state, t, dt, acceleration),
+            Self::DormandPrince {
+                tolerance,
+                min_step,
+            } => DormandPrince {
+                tolerance,
+                min_step,
+            }
+            .integrate(state, t, dt, acceleration),
+            Self::Verlet => Verlet.integrate(state, t, dt, acceleration),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // gravity and quadratic drag of a pitch
+    fn acceleration(_t: f64, state: &PointState) -> DVec3 {
+        DVec3::NEG_Z * 9.81 - state.velocity * (0.005 * state.velocity.length())
+    }
+
+    fn fly(integrator: &impl Integrator, dt: f64) -> PointState {
+        let mut state = PointState {
+            position: DVec3::new(0., 0., 1.8),
+            velocity: DVec3::new(0., 42., -1.),
+        };
+        let steps = (0.4 / dt).round() as usize;
+        for i in 0..steps {
+            state = integrator.integrate(state, i as f64 * dt, dt, &acceleration);
+        }
+        state
+    }
+
+    #[test]
+    fn rk4_and_dormand_prince_agree() {
+        let rk4 = fly(&Rk4, 1. / 240.);
+        let dormand_prince = fly(&DormandPrince::default(), 1. / 240.);
+        assert!(rk4.position.distance(dormand_prince.position) < 1e-6);
+        assert!(rk4.velocity.distance(dormand_prince.velocity) < 1e-6);
+    }
+
+    #[test]
+    fn euler_error_shrinks_with_the_step() {
+        let reference = fly(&Rk4, 1e-4);
+        let errors = [1. / 60., 1. / 120., 1. / 240.]
+            .map(|dt| fly(&Euler, dt).position.distance(reference.position));
+        // first order: halving the step about halves the error
+        for pair in errors.windows(2) {
+            assert!(pair[1] < 0.6 * pair[0], "{errors:?}");
+        }
+    }
+
+    #[test]
+    fn dormand_prince_ends_without_a_positive_min_step() {
+        let integrator = DormandPrince {
+            tolerance: 0.,
+            min_step: 0.,
+        };
+        let state = fly(&integrator, 1. / 240.);
+        assert!(state.position.distance(fly(&Rk4, 1. / 240.).position) < 1e-6);
+    }
+}
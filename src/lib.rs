@@ -1,50 +1,96 @@
+mod aerodynamics;
+mod assets;
 mod ball_flight_state;
 mod common;
 mod components;
 mod errors;
 mod events;
+mod integrators;
+mod metrics;
+mod pitch;
 mod resources;
+mod seams;
+mod simulation;
+mod statcast;
 mod systems;
+mod trajectory;
 
 pub mod prelude {
     pub use super::{
-        ball_flight_state::BaseballFlightState, components::*, constants::*, errors::*, events::*,
-        utils::*, BaseballFlightPlugin, GyroPole, Tilt,
+        aerodynamics::*,
+        assets::BaseballPluginConfigLoader,
+        ball_flight_state::BaseballFlightState,
+        components::*,
+        constants::*,
+        errors::*,
+        events::*,
+        integrators::*,
+        metrics::*,
+        pitch::*,
+        resources::{
+            Atmosphere, BaseballPluginConfig, BaseballPluginConfigHandle, FieldFrame,
+            SeamShiftedWake, Wind, WindField,
+        },
+        seams::{baseball_mesh, baseball_texture, BaseballSeamMesh, SeamOrientation},
+        simulation::*,
+        statcast::*,
+        trajectory::*,
+        utils::*,
+        BaseballFlightPlugin, GyroPole, Tilt,
     };
 }
 
 use crate::systems::*;
 
 pub(crate) use crate::resources::*;
+pub(crate) use aerodynamics::*;
+pub(crate) use assets::*;
 pub(crate) use ball_flight_state::*;
 pub(crate) use bevy::{math::*, prelude::*}; // glam
 pub(crate) use bevy_rapier3d::prelude::*; // nalgebra
 pub(crate) use common::*;
+pub(crate) use components::*;
 pub(crate) use constants::*;
 pub(crate) use errors::*;
 pub(crate) use events::*;
+pub(crate) use integrators::*;
+pub(crate) use metrics::*;
+pub(crate) use seams::*;
+pub(crate) use simulation::*;
+pub(crate) use trajectory::*;
 pub(crate) use utils::*;
 
+#[derive(Default)]
 pub struct BaseballFlightPlugin {
-    pub ssw_on: bool,
-    pub magnus_on: bool,
-    pub drag_on: bool,
+    pub config: BaseballPluginConfig,
+    // `.aero.ron` asset the config is loaded from and hot reloaded with. requires the `AssetPlugin`
+    pub config_path: Option<String>,
 }
 
 impl Plugin for BaseballFlightPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<ActivateAerodynamicsEvent>()
             .add_event::<PostActivateAerodynamicsEvent>()
-            .add_event::<DisableAerodynamicsEvent>();
+            .add_event::<DisableAerodynamicsEvent>()
+            .add_event::<TrajectoryRecordedEvent>()
+            .add_event::<PlateCrossedEvent>();
 
         // app.register_type::<BaseballFlightState>();
+        app.register_type::<AeroForces>()
+            .register_type::<AeroOverrides>()
+            .register_type::<Atmosphere>()
+            .register_type::<BallProperties>()
+            .register_type::<BaseballPluginConfig>()
+            .register_type::<FieldFrame>()
+            .register_type::<FlightIntegrator>()
+            .register_type::<PitchMetrics>()
+            .register_type::<BaseballSeamMesh>()
+            .register_type::<SeamShiftedWake>();
 
-        app.insert_resource(BaseballPluginConfig {
-            ssw_on: self.ssw_on,
-            magnus_on: self.magnus_on,
-            drag_on: self.drag_on,
-            ..default()
-        });
+        app.insert_resource(self.config.clone());
+        app.init_resource::<Atmosphere>()
+            .init_resource::<Wind>()
+            .init_resource::<FieldFrame>();
 
         app.configure_sets(
             Update,
@@ -95,8 +141,49 @@ impl Plugin for BaseballFlightPlugin {
                 _apply_physics_option_3
             )
                 .in_set(UpdateBaseballFlightStateSet::Update),
+        )
+        .add_systems(
+            Update,
+            (
+                update_aero_forces,
+                sync_seam_mesh,
+                send_plate_crossed_events,
+            )
+                .in_set(UpdateBaseballFlightStateSet::PostUpdate),
+        )
+        .add_systems(
+            Update,
+            (record_trajectories, compute_pitch_metrics)
+                .chain()
+                .in_set(UpdateBaseballFlightStateSet::PostUpdate)
+                .after(AeroDeactivationSet::Deactivation),
         );
     }
+
+    // the asset support is registered once all plugins are added, so the `AssetPlugin`
+    // may be added before or after this plugin
+    fn finish(&self, app: &mut App) {
+        if !app.is_plugin_added::<AssetPlugin>() {
+            if let Some(path) = &self.config_path {
+                warn!("config {:?} is not loaded without the AssetPlugin", path);
+            }
+            return;
+        }
+
+        app.init_asset::<BaseballPluginConfig>()
+            .init_asset_loader::<BaseballPluginConfigLoader>()
+            .add_systems(PreUpdate, sync_config_asset);
+        if let Some(path) = self.config_path.clone() {
+            app.add_systems(
+                Startup,
+                move |mut commands: Commands, asset_server: Res<AssetServer>| {
+                    commands.insert_resource(BaseballPluginConfigHandle(
+                        asset_server.load(path.clone()),
+                    ));
+                },
+            );
+        }
+    }
 }
 
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
@@ -134,6 +221,14 @@ impl Default for GyroPole {
 
 #[derive(Debug, Reflect, Copy, Clone)]
 pub struct Tilt(f32);
+
+impl Default for Tilt {
+    // 12:00, pure backspin
+    fn default() -> Self {
+        Self(3. * PI_32 / 2.)
+    }
+}
+
 impl Tilt {
     pub fn from_hour_mintes(h: i8, m: i8) -> Result<Self> {
         if h > 12 || h <= 0 {
@@ -151,6 +246,16 @@ impl Tilt {
         Ok(Self(rad_hrs + rad_mins))
     }
 
+    /// statcast spin axis in degrees. 180 is pure backspin (12:00) and 270 is 3:00
+    pub fn from_spin_axis(spin_axis: f32) -> Self {
+        Self((spin_axis + 90.).to_radians().rem_euclid(2. * PI_32))
+    }
+
+    /// statcast spin axis in degrees
+    pub fn to_spin_axis(&self) -> f32 {
+        (self.0.to_degrees() - 90.).rem_euclid(360.)
+    }
+
     pub fn to_hour_minutes(&self) -> (i8, i8) {
         let total_hours = (self.0 * 6.0 / PI_32) + 3.0;
         let hrs = total_hours.floor() as i8;
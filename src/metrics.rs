@@ -0,0 +1,200 @@
+use crate::*;
+
+/// movement and spin metrics of a pitch.
+/// breaks are measured at the front of home plate against a flight of the same release under gravity and
+/// drag only (no magnus or ssw) after the same time of flight, and are in inches, in field coordinates.
+/// inserted on balls with a `TrajectoryRecorder` once their flight is complete, other balls get no metrics
+#[derive(Debug, Clone, Copy, Component, Reflect)]
+#[reflect(Component)]
+pub struct PitchMetrics {
+    // vertical movement caused by the aerodynamic forces. positive is upwards
+    pub induced_vertical_break: f32,
+    // positive is towards the catcher's right (field +x)
+    pub horizontal_break: f32,
+    // length of the combined induced vertical and horizontal break
+    pub total_break: f32,
+    // drop from the release point to the front of home plate, gravity included
+    pub total_vertical_drop: f32,
+    // tilt of the spin at release
+    pub spin_based_tilt: Tilt,
+    // tilt inferred from the direction of the break
+    pub observed_tilt: Tilt,
+    // percentage of the spin at release perpendicular to the velocity
+    pub active_spin: f32,
+    // time from release to the front of home plate in s
+    pub plate_time: f32,
+}
+
+impl PitchMetrics {
+    /// the first sample of `trajectory` is taken as the release. `gravity` is in the bevy world
+    pub fn from_trajectory(
+        trajectory: &Trajectory,
+        field_frame: &FieldFrame,
+        gravity: Vec3,
+    ) -> Result<Self> {
+        let Some(release) = trajectory.first() else {
+            return Err(BaseballFlightError::InvalidInput(
+                "trajectory should not be empty".into(),
+            ));
+        };
+        let (crossing_time, plate_position) = plate_crossing(trajectory, field_frame)?;
+        let plate_time = crossing_time - release.time as f32;
+
+        // flight from the release over the same time under gravity and the drag of the trajectory
+        let position = field_frame.to_field(release.position);
+        let velocity = field_frame.direction_to_field(release.velocity);
+        let gravity = field_frame.direction_to_field(gravity);
+        let reference_position = position
+            + velocity * plate_time
+            + gravity * (0.5 * plate_time.powi(2))
+            + field_frame.direction_to_field(drag_displacement(trajectory, crossing_time as f64));
+
+        let break_ = (plate_position - reference_position) * M_TO_INCHES;
+        let spin = field_frame.direction_to_field(release.spin);
+        let active_spin = match spin.try_normalize() {
+            Some(_) => {
+                let direction = velocity.normalize_or_zero();
+                (spin - direction * spin.dot(direction)).length() / spin.length() * 100.
+            }
+            None => 0.,
+        };
+
+        Ok(Self {
+            induced_vertical_break: break_.z,
+            horizontal_break: break_.x,
+            total_break: Vec2::new(break_.x, break_.z).length(),
+            total_vertical_drop: (position.z - plate_position.z) * M_TO_INCHES,
+            // inverse of `get_angular_velocity_from_parameters`
+            spin_based_tilt: Tilt(spin.x.atan2(-spin.z).rem_euclid(2. * PI_32)),
+            // a tilt of 3:00 breaks towards field -x, 12:00 upwards
+            observed_tilt: Tilt((-break_.z).atan2(-break_.x).rem_euclid(2. * PI_32)),
+            active_spin,
+            plate_time,
+        })
+    }
+
+    /// simulates the pitch with `simulate_pitch` and measures it
+    pub fn simulate(params: &PitchParams, config: &SimulationConfig) -> Result<Self> {
+        let trajectory = simulate_pitch(params, config)?;
+        Self::from_trajectory(&trajectory, &config.field_frame, config.gravity)
+    }
+}
+
+// time (relative to the trajectory) and field position at the front of home plate, interpolated between samples
+fn plate_crossing(trajectory: &Trajectory, field_frame: &FieldFrame) -> Result<(f32, Vec3)> {
+    trajectory
+        .samples()
+        .windows(2)
+        .find_map(|pair| {
+            let before = field_frame.to_field(pair[0].position);
+            let after = field_frame.to_field(pair[1].position);
+            if before.y >= PLATE_FRONT && after.y < PLATE_FRONT {
+                let fraction = (before.y - PLATE_FRONT) / (before.y - after.y);
+                let time = pair[0].time + (pair[1].time - pair[0].time) * fraction as f64;
+                Some((time as f32, before.lerp(after, fraction)))
+            } else {
+                None
+            }
+        })
+        .ok_or_else(|| {
+            BaseballFlightError::InvalidInput("trajectory should reach home plate".into())
+        })
+}
+
+// displacement caused by drag from the first sample until `end_time`, in the bevy world
+fn drag_displacement(trajectory: &Trajectory, end_time: f64) -> Vec3 {
+    let (mut velocity, mut displacement) = (Vec3::ZERO, Vec3::ZERO);
+    for pair in trajectory.samples().windows(2) {
+        if pair[0].time >= end_time {
+            break;
+        }
+        // the accelerations of a sample act over the interval leading up to it
+        let dt = (pair[1].time.min(end_time) - pair[0].time) as f32;
+        let next_velocity = velocity + pair[1].accelerations.drag * dt;
+        displacement += (velocity + next_velocity) * (0.5 * dt);
+        velocity = next_velocity;
+    }
+    displacement
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pitch::PitchRelease;
+
+    fn metrics(tilt: Tilt, config: &SimulationConfig) -> PitchMetrics {
+        let release = PitchRelease::builder()
+            .velocity(94.)
+            .spin_rate(2300.)
+            .tilt(tilt)
+            .build()
+            .unwrap();
+        PitchMetrics::simulate(&release.to_pitch_params(&config.field_frame), config).unwrap()
+    }
+
+    // within 5 degrees, or about 10 minutes on the clock
+    fn assert_tilt(actual: Tilt, expected: Tilt) {
+        let difference = (actual.get() - expected.get()).rem_euclid(2. * PI_32);
+        assert!(
+            difference.min(2. * PI_32 - difference) < 5f32.to_radians(),
+            "{:?} and {:?}",
+            actual.to_hour_minutes(),
+            expected.to_hour_minutes()
+        );
+    }
+
+    #[test]
+    fn gravity_only_flight_has_no_break() {
+        let mut config = SimulationConfig::default();
+        config.aerodynamics.drag_on = false;
+        config.aerodynamics.magnus_on = false;
+        config.aerodynamics.ssw_on = false;
+        let metrics = metrics(Tilt::default(), &config);
+        assert!(metrics.total_break < 0.05, "{metrics:?}");
+        assert!(metrics.total_vertical_drop > 0.);
+        assert!((metrics.active_spin - 100.).abs() < 0.1);
+    }
+
+    #[test]
+    fn drag_does_not_break() {
+        let mut config = SimulationConfig::default();
+        config.aerodynamics.magnus_on = false;
+        config.aerodynamics.ssw_on = false;
+        let metrics = metrics(Tilt::default(), &config);
+        assert!(metrics.induced_vertical_break.abs() < 0.05, "{metrics:?}");
+        assert!(metrics.horizontal_break.abs() < 0.05, "{metrics:?}");
+    }
+
+    #[test]
+    fn break_follows_the_tilt() {
+        let mut config = SimulationConfig::default();
+        config.aerodynamics.ssw_on = false;
+
+        let backspin = Tilt::from_hour_mintes(12, 0).unwrap();
+        let metrics_backspin = metrics(backspin, &config);
+        assert!(
+            metrics_backspin.induced_vertical_break > 10.,
+            "{metrics_backspin:?}"
+        );
+        assert!(
+            metrics_backspin.horizontal_break.abs() < 0.5,
+            "{metrics_backspin:?}"
+        );
+        assert_tilt(metrics_backspin.spin_based_tilt, backspin);
+        assert_tilt(metrics_backspin.observed_tilt, backspin);
+
+        let arm_side = Tilt::from_hour_mintes(2, 0).unwrap();
+        let metrics_arm_side = metrics(arm_side, &config);
+        // 2:00 breaks mostly towards field -x and partly upwards
+        assert!(
+            metrics_arm_side.horizontal_break < -10.,
+            "{metrics_arm_side:?}"
+        );
+        assert!(
+            metrics_arm_side.induced_vertical_break > 0.,
+            "{metrics_arm_side:?}"
+        );
+        assert_tilt(metrics_arm_side.spin_based_tilt, arm_side);
+        assert_tilt(metrics_arm_side.observed_tilt, arm_side);
+    }
+}
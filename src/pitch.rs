@@ -0,0 +1,239 @@
+use crate::*;
+
+use bevy::ecs::system::EntityCommands;
+use bevy::ecs::world::Command;
+
+/// release parameters of a pitch
+#[derive(Debug, Clone)]
+pub struct PitchRelease {
+    // release point in field coordinates (m)
+    pub release_point: Vec3,
+    // in mph
+    pub velocity: f32,
+    // in degrees. positive is upwards
+    pub vertical_angle: f32,
+    // in degrees. positive is towards the catcher's right (field +x)
+    pub horizontal_angle: f32,
+    // in rpm
+    pub spin_rate: f32,
+    pub tilt: Tilt,
+    // in the range of 0 and 1
+    pub spin_efficiency: f32,
+    pub gyro_pole: GyroPole,
+    pub seam_orientation: SeamOrientation,
+    pub ball: BallProperties,
+    // front and back panels of the strikezone as distances from home plate along the field's y axis in m
+    pub strikezone_panels_y: (f32, f32),
+}
+
+impl PitchRelease {
+    pub fn builder() -> PitchReleaseBuilder {
+        PitchReleaseBuilder::default()
+    }
+
+    /// release point in the bevy world
+    pub fn translation(&self, field_frame: &FieldFrame) -> Vec3 {
+        field_frame.to_world(self.release_point)
+    }
+
+    /// release velocity in the bevy world (m/s)
+    pub fn linear_velocity(&self, field_frame: &FieldFrame) -> Vec3 {
+        let vertical = self.vertical_angle.to_radians();
+        let horizontal = self.horizontal_angle.to_radians();
+        let direction = Vec3::new(
+            vertical.cos() * horizontal.sin(),
+            -vertical.cos() * horizontal.cos(),
+            vertical.sin(),
+        );
+        field_frame.direction_to_world(direction * self.velocity * MPH_TO_MS)
+    }
+
+    /// spin in the bevy world (rad/s)
+    pub fn angular_velocity(&self, field_frame: &FieldFrame) -> Vec3 {
+        field_frame.direction_to_world(get_angular_velocity_from_parameters(
+            self.tilt,
+            self.spin_efficiency,
+            self.spin_rate,
+            self.gyro_pole,
+        ))
+    }
+
+    /// initial conditions for `simulate_pitch`
+    pub fn to_pitch_params(&self, field_frame: &FieldFrame) -> PitchParams {
+        PitchParams {
+            position: self.translation(field_frame),
+            velocity: self.linear_velocity(field_frame),
+            spin: self.angular_velocity(field_frame),
+            seam_orientation: self.seam_orientation,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PitchReleaseBuilder {
+    release: PitchRelease,
+}
+
+impl Default for PitchReleaseBuilder {
+    fn default() -> Self {
+        Self {
+            release: PitchRelease {
+                // right handed pitcher's release point
+                release_point: Vec3::new(-0.48, 16.764, 1.82),
+                velocity: 90.,
+                vertical_angle: 0.,
+                horizontal_angle: 0.,
+                spin_rate: 2200.,
+                tilt: Tilt::default(),
+                spin_efficiency: 1.,
+                gyro_pole: GyroPole::default(),
+                seam_orientation: SeamOrientation::default(),
+                ball: BallProperties::default(),
+                strikezone_panels_y: (PLATE_FRONT, 0.),
+            },
+        }
+    }
+}
+
+impl PitchReleaseBuilder {
+    /// in field coordinates (m)
+    pub fn release_point(mut self, release_point: Vec3) -> Self {
+        self.release.release_point = release_point;
+        self
+    }
+
+    /// in mph
+    pub fn velocity(mut self, velocity: f32) -> Self {
+        self.release.velocity = velocity;
+        self
+    }
+
+    /// vertical and horizontal release angles in degrees
+    pub fn release_angles(mut self, vertical_angle: f32, horizontal_angle: f32) -> Self {
+        self.release.vertical_angle = vertical_angle;
+        self.release.horizontal_angle = horizontal_angle;
+        self
+    }
+
+    /// in rpm
+    pub fn spin_rate(mut self, spin_rate: f32) -> Self {
+        self.release.spin_rate = spin_rate;
+        self
+    }
+
+    pub fn tilt(mut self, tilt: Tilt) -> Self {
+        self.release.tilt = tilt;
+        self
+    }
+
+    pub fn spin_efficiency(mut self, spin_efficiency: f32) -> Self {
+        self.release.spin_efficiency = spin_efficiency;
+        self
+    }
+
+    pub fn gyro_pole(mut self, gyro_pole: GyroPole) -> Self {
+        self.release.gyro_pole = gyro_pole;
+        self
+    }
+
+    pub fn seam_orientation(mut self, seam_orientation: SeamOrientation) -> Self {
+        self.release.seam_orientation = seam_orientation;
+        self
+    }
+
+    /// raw seam angles in rad. see `SeamOrientation::Custom`
+    pub fn seam_angles(self, y_angle: f32, z_angle: f32) -> Self {
+        self.seam_orientation(SeamOrientation::Custom { y_angle, z_angle })
+    }
+
+    pub fn ball(mut self, ball: BallProperties) -> Self {
+        self.release.ball = ball;
+        self
+    }
+
+    /// distances of the front and back strikezone panels from home plate in m
+    pub fn strikezone_panels_y(mut self, front: f32, back: f32) -> Self {
+        self.release.strikezone_panels_y = (front, back);
+        self
+    }
+
+    pub fn build(self) -> Result<PitchRelease> {
+        let release = self.release;
+        if !release.release_point.is_finite() {
+            return Err(BaseballFlightError::InvalidInput(
+                "release point should be finite".into(),
+            ));
+        }
+        if !release.velocity.is_finite() || release.velocity <= 0. {
+            return Err(BaseballFlightError::InvalidInput(
+                "velocity should be positive".into(),
+            ));
+        }
+        if release.vertical_angle.abs() >= 90. || release.horizontal_angle.abs() >= 90. {
+            return Err(BaseballFlightError::InvalidInput(
+                "release angles should be within the range of -90 and 90 degrees".into(),
+            ));
+        }
+        if !release.spin_rate.is_finite() || release.spin_rate < 0. {
+            return Err(BaseballFlightError::InvalidInput(
+                "spin rate should not be negative".into(),
+            ));
+        }
+        if !(0. ..=1.).contains(&release.spin_efficiency) {
+            return Err(BaseballFlightError::InvalidInput(
+                "spin efficiency should be within the range of 0 and 1".into(),
+            ));
+        }
+        Ok(release)
+    }
+}
+
+pub trait SpawnPitchExt {
+    /// spawns a ball at the release point and activates its aerodynamics
+    fn spawn_pitch(&mut self, release: PitchRelease) -> EntityCommands<'_>;
+}
+
+impl SpawnPitchExt for Commands<'_, '_> {
+    fn spawn_pitch(&mut self, release: PitchRelease) -> EntityCommands<'_> {
+        let entity = self.spawn_empty().id();
+        self.add(SpawnPitch { entity, release });
+        self.entity(entity)
+    }
+}
+
+struct SpawnPitch {
+    entity: Entity,
+    release: PitchRelease,
+}
+
+impl Command for SpawnPitch {
+    fn apply(self, world: &mut World) {
+        let field_frame = world
+            .get_resource::<FieldFrame>()
+            .copied()
+            .unwrap_or_default();
+        let release = self.release;
+
+        let Some(mut entity) = world.get_entity_mut(self.entity) else {
+            return;
+        };
+        entity.insert((
+            BaseballFlightBundle::from_properties(release.ball),
+            ExternalForce::default(),
+            TransformBundle::from_transform(Transform::from_translation(
+                release.translation(&field_frame),
+            )),
+            Velocity {
+                linvel: release.linear_velocity(&field_frame),
+                angvel: release.angular_velocity(&field_frame),
+            },
+        ));
+
+        world.send_event(ActivateAerodynamicsEvent {
+            entity: self.entity,
+            seam_orientation: release.seam_orientation,
+            record_times: vec![],
+            strikezone_panels_z: release.strikezone_panels_y,
+        });
+    }
+}
@@ -1,17 +1,30 @@
 use crate::*;
 
-#[derive(Debug, Resource)]
-pub(crate) struct BaseballPluginConfig {
+use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, fmt, sync::Arc};
+
+/// aerodynamics configuration shared by all balls. can be modified at runtime,
+/// or loaded from a `.aero.ron` asset (see `BaseballPluginConfigHandle`)
+#[derive(Debug, Clone, Resource, Asset, Reflect, Serialize, Deserialize)]
+#[reflect(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BaseballPluginConfig {
     pub ssw_on: bool,
     pub magnus_on: bool,
     pub drag_on: bool,
     pub ssw: SeamShiftedWake,
+    pub integrator: IntegratorKind,
+    pub drag_model: DragModelKind,
+    pub lift_model: LiftModelKind,
+    pub spin_decay: SpinDecayModel,
+    pub knuckleball: KnuckleballModel,
 }
 
-// probably should be a resource
-/// seam shifted wake parameters
-#[derive(Debug, Copy, Clone)]
-pub(crate) struct SeamShiftedWake {
+/// seam shifted wake parameters. insert as a component to override the global parameters for a single ball
+#[derive(Debug, Copy, Clone, Component, Reflect, Serialize, Deserialize)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeamShiftedWake {
     // this number effects how much the separation location will change based on the spin rate. Bigger, Move shift allows for the moving the effectiveness of the seams forwards or backwards.
     pub seam_shift_factor: f64,
     // in rad
@@ -20,6 +33,8 @@ pub(crate) struct SeamShiftedWake {
     pub activation_shift: f64,
     // in rad
     pub separated_flow_range: f64,
+    // lift coefficient per seam per length (in inches) away from the origin
+    pub seam_coefficient: f64,
 }
 
 impl Default for SeamShiftedWake {
@@ -29,14 +44,16 @@ impl Default for SeamShiftedWake {
             angle_of_activation: 5. * PI_64 / 180.,
             activation_shift: 0.21,
             separated_flow_range: 35. * PI_64 / 180.,
+            seam_coefficient: C_SEAMS,
         }
     }
 }
 
 impl SeamShiftedWake {
-    pub(crate) fn get_activation_region(&self) -> (DVec3, DVec3) {
-        let acceptable_range = SEAM_DIAMETER * 1.1;
-        let acceptable_thickness = SEAM_DIAMETER / 2. * (2. * self.angle_of_activation).sin();
+    // seam diameter in inches
+    pub(crate) fn get_activation_region(&self, seam_diameter: f64) -> (DVec3, DVec3) {
+        let acceptable_range = seam_diameter * 1.1;
+        let acceptable_thickness = seam_diameter / 2. * (2. * self.angle_of_activation).sin();
 
         let x_max = 0.5 * acceptable_range;
         let x_min = -0.5 * acceptable_range;
@@ -59,6 +76,273 @@ impl Default for BaseballPluginConfig {
             magnus_on: true,
             drag_on: true,
             ssw: SeamShiftedWake::default(),
+            integrator: IntegratorKind::default(),
+            drag_model: DragModelKind::default(),
+            lift_model: LiftModelKind::default(),
+            spin_decay: SpinDecayModel::default(),
+            knuckleball: KnuckleballModel::default(),
+        }
+    }
+}
+
+impl BaseballPluginConfig {
+    // configuration of a single ball with its optional overrides applied
+    pub(crate) fn for_ball(
+        &self,
+        ssw: Option<&SeamShiftedWake>,
+        overrides: Option<&AeroOverrides>,
+    ) -> Cow<'_, Self> {
+        if ssw.is_none() && overrides.is_none() {
+            return Cow::Borrowed(self);
+        }
+        let mut config = self.clone();
+        if let Some(ssw) = ssw {
+            config.ssw = *ssw;
+        }
+        if let Some(overrides) = overrides {
+            overrides.apply(&mut config);
+        }
+        Cow::Owned(config)
+    }
+}
+
+/// config asset the `BaseballPluginConfig` resource is kept in sync with, including hot reloads
+#[derive(Debug, Clone, Default, Resource)]
+pub struct BaseballPluginConfigHandle(pub Handle<BaseballPluginConfig>);
+
+/// atmospheric conditions of the ballpark. air density is derived from these at runtime,
+/// so the resource can be changed mid-session (e.g. when switching ballparks)
+#[derive(Debug, Copy, Clone, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct Atmosphere {
+    // in celsius
+    pub temperature: f32,
+    // barometric pressure in hPa, corrected to sea level as reported by weather services
+    pub pressure: f32,
+    // in the range of 0 and 1
+    pub relative_humidity: f32,
+    // elevation above sea level in m
+    pub elevation: f32,
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        // 70°F standard day at sea level
+        Self {
+            temperature: 21.1,
+            pressure: 1013.25,
+            relative_humidity: 0.5,
+            elevation: 0.,
+        }
+    }
+}
+
+impl Atmosphere {
+    /// temperature in fahrenheit, pressure in inHg and elevation in ft
+    pub fn from_imperial(
+        temperature_f: f32,
+        pressure_in_hg: f32,
+        relative_humidity: f32,
+        elevation_ft: f32,
+    ) -> Self {
+        Self {
+            temperature: (temperature_f - 32.) * 5. / 9.,
+            pressure: pressure_in_hg * 33.8639,
+            relative_humidity,
+            elevation: elevation_ft / M_TO_FEET,
+        }
+    }
+
+    /// saturation vapor pressure in hPa (Buck equation)
+    pub fn saturation_vapor_pressure(&self) -> f32 {
+        let t = self.temperature;
+        6.1121 * ((18.678 - t / 234.5) * (t / (257.14 + t))).exp()
+    }
+
+    /// barometric pressure at the elevation of the ballpark in hPa
+    pub fn station_pressure(&self) -> f32 {
+        self.pressure * (-0.0001217 * self.elevation).exp()
+    }
+
+    /// moist air density in kg/m^3
+    pub fn air_density(&self) -> f32 {
+        let partial_vapor_pressure =
+            0.3783 * self.relative_humidity * self.saturation_vapor_pressure();
+        1.2929
+            * (273.15 / (self.temperature + 273.15))
+            * (self.station_pressure() - partial_vapor_pressure)
+            / 1013.25
+    }
+
+    /// dynamic viscosity of air in Pa·s (Sutherland's law)
+    pub fn dynamic_viscosity(&self) -> f32 {
+        let t = self.temperature + 273.15;
+        1.716e-5 * (t / 273.15).powf(1.5) * (273.15 + 110.4) / (t + 110.4)
+    }
+}
+
+/// user defined wind velocity field
+pub trait WindField: Send + Sync + 'static {
+    /// wind velocity in bevy coordinates (m/s) at `position` and `time` (in seconds).
+    /// `time` is the time of flight since the release, both in the plugin and in `simulate_pitch`
+    fn sample(&self, position: Vec3, time: f32) -> Vec3;
+}
+
+/// wind blowing through the ballpark. all velocities are in bevy coordinates and m/s
+#[derive(Clone, Resource, Default)]
+pub enum Wind {
+    #[default]
+    Calm,
+    Constant(Vec3),
+    /// logarithmic wind profile (wind shear) over the ground plane of the field (field z = 0)
+    LogProfile {
+        // wind velocity measured at `reference_height`
+        reference_velocity: Vec3,
+        // in m
+        reference_height: f32,
+        // in m. roughly 0.03 for a grass field
+        roughness_length: f32,
+    },
+    Field(Arc<dyn WindField>),
+}
+
+impl fmt::Debug for Wind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Calm => write!(f, "Calm"),
+            Self::Constant(velocity) => f.debug_tuple("Constant").field(velocity).finish(),
+            Self::LogProfile {
+                reference_velocity,
+                reference_height,
+                roughness_length,
+            } => f
+                .debug_struct("LogProfile")
+                .field("reference_velocity", reference_velocity)
+                .field("reference_height", reference_height)
+                .field("roughness_length", roughness_length)
+                .finish(),
+            Self::Field(_) => write!(f, "Field(..)"),
+        }
+    }
+}
+
+impl Wind {
+    /// log profile with the reference velocity measured at 10 m over a grass field
+    pub fn log_profile(reference_velocity: Vec3) -> Self {
+        Self::LogProfile {
+            reference_velocity,
+            reference_height: 10.,
+            roughness_length: 0.03,
         }
     }
+
+    pub fn from_field(field: impl WindField) -> Self {
+        Self::Field(Arc::new(field))
+    }
+
+    /// wind velocity in bevy coordinates (m/s) at `position` and time of flight `time` (in seconds).
+    /// the height of the log profile is measured in the field frame
+    pub fn sample(&self, position: Vec3, time: f32, field_frame: &FieldFrame) -> Vec3 {
+        match self {
+            Self::Calm => Vec3::ZERO,
+            Self::Constant(velocity) => *velocity,
+            Self::LogProfile {
+                reference_velocity,
+                reference_height,
+                roughness_length,
+            } => {
+                let height = field_frame.to_field(position).z;
+                if height <= *roughness_length {
+                    return Vec3::ZERO;
+                }
+                *reference_velocity * (height / roughness_length).ln()
+                    / (reference_height / roughness_length).ln()
+            }
+            Self::Field(field) => field.sample(position, time),
+        }
+    }
+}
+
+/// position and orientation of home plate in the bevy world.
+///
+/// field coordinates are in m with the origin at the point of home plate,
+/// +y towards the pitcher's mound, +z up and +x towards the catcher's right (first base side)
+#[derive(Debug, Copy, Clone, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct FieldFrame {
+    pub home_plate: Vec3,
+    // rotation around home plate. identity places the pitcher's mound along bevy's +z axis
+    pub rotation: Quat,
+}
+
+impl Default for FieldFrame {
+    fn default() -> Self {
+        Self {
+            home_plate: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+        }
+    }
+}
+
+impl FieldFrame {
+    pub fn new(home_plate: Vec3, rotation: Quat) -> Self {
+        Self {
+            home_plate,
+            rotation,
+        }
+    }
+
+    /// home plate facing the pitcher's mound. only the horizontal direction is taken into account
+    pub fn facing(home_plate: Vec3, mound: Vec3) -> Self {
+        let direction = mound - home_plate;
+        Self {
+            home_plate,
+            rotation: Quat::from_rotation_y(direction.x.atan2(direction.z)),
+        }
+    }
+
+    /// bevy world position to field coordinates
+    pub fn to_field(self, position: Vec3) -> Vec3 {
+        self.direction_to_field(position - self.home_plate)
+    }
+
+    /// field coordinates to bevy world position
+    pub fn to_world(self, position: Vec3) -> Vec3 {
+        self.home_plate + self.direction_to_world(position)
+    }
+
+    /// rotates vectors (velocity, spin, ...) from the bevy world into field coordinates
+    pub fn direction_to_field(&self, direction: Vec3) -> Vec3 {
+        swap_coordinates_vec3(&self.rotation.inverse().mul_vec3(direction))
+    }
+
+    /// rotates vectors (velocity, spin, ...) from field coordinates into the bevy world
+    pub fn direction_to_world(&self, direction: Vec3) -> Vec3 {
+        self.rotation.mul_vec3(swap_coordinates_vec3(&direction))
+    }
+
+    // rotation from field coordinates into the bevy world
+    pub(crate) fn world_rotation_d(&self) -> DQuat {
+        // `swap_coordinates` is a proper rotation
+        let swap = DQuat::from_mat3(&DMat3::from_cols(DVec3::NEG_X, DVec3::Z, DVec3::Y));
+        self.rotation.as_dquat() * swap
+    }
+
+    pub(crate) fn to_field_d(self, position: DVec3) -> DVec3 {
+        self.direction_to_field_d(position - self.home_plate.as_dvec3())
+    }
+
+    pub(crate) fn to_world_d(self, position: DVec3) -> DVec3 {
+        self.home_plate.as_dvec3() + self.direction_to_world_d(position)
+    }
+
+    pub(crate) fn direction_to_field_d(&self, direction: DVec3) -> DVec3 {
+        swap_coordinates_dvec3(&self.rotation.as_dquat().inverse().mul_vec3(direction))
+    }
+
+    pub(crate) fn direction_to_world_d(&self, direction: DVec3) -> DVec3 {
+        self.rotation
+            .as_dquat()
+            .mul_vec3(swap_coordinates_dvec3(&direction))
+    }
 }
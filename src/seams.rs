@@ -0,0 +1,169 @@
+use crate::*;
+
+use serde::{Deserialize, Serialize};
+
+use bevy::render::{
+    render_asset::RenderAssetUsages,
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+// leather and stitches in srgb
+const LEATHER_COLOR: [u8; 4] = [242, 238, 228, 255];
+const STITCH_COLOR: [u8; 4] = [200, 30, 40, 255];
+const SEAM_COLOR: [u8; 4] = [215, 205, 190, 255];
+// angular half widths of the stitches and the seam groove in rad
+const STITCH_HALF_WIDTH: f32 = 0.08;
+const SEAM_HALF_WIDTH: f32 = 0.02;
+
+/// seam curve on the unit sphere in the local frame of the ball
+pub(crate) fn seam_curve(n_points: usize) -> Vec<DVec3> {
+    (0..n_points)
+        .map(|i| {
+            let alpha = (PI_64 * 2.) * (i as f64 / n_points as f64);
+            let x = (1. / 13.) * (9. * f64::cos(alpha) - 4. * f64::cos(3. * alpha));
+            let y = (1. / 13.) * (9. * f64::sin(alpha) + 4. * f64::sin(3. * alpha));
+            let z = (12. / 13.) * f64::cos(2. * alpha);
+            DVec3::new(x, y, z)
+        })
+        .collect()
+}
+
+/// uv sphere in the local frame of the ball. use with `baseball_texture`
+pub fn baseball_mesh(radius: f32) -> Mesh {
+    Sphere::new(radius).mesh().uv(64, 32)
+}
+
+/// equirectangular texture for `baseball_mesh` with the stitches painted along the simulated seam curve
+pub fn baseball_texture(width: u32, height: u32) -> Image {
+    let seams = seam_curve(N_SEAMS * 4)
+        .iter()
+        .map(|point| point.as_vec3())
+        .collect::<Vec<_>>();
+
+    let data = (0..height)
+        .flat_map(|row| (0..width).map(move |column| (row, column)))
+        .flat_map(|(row, column)| {
+            // same mapping as the uv sphere: u along the longitude from +x to +y, v from +z to -z
+            let longitude = 2. * PI_32 * (column as f32 + 0.5) / width as f32;
+            let latitude = PI_32 / 2. - PI_32 * (row as f32 + 0.5) / height as f32;
+            let direction = Vec3::new(
+                latitude.cos() * longitude.cos(),
+                latitude.cos() * longitude.sin(),
+                latitude.sin(),
+            );
+            let distance = seams
+                .iter()
+                .map(|seam| direction.dot(*seam))
+                .fold(-1., f32::max)
+                .clamp(-1., 1.)
+                .acos();
+            if distance < SEAM_HALF_WIDTH {
+                SEAM_COLOR
+            } else if distance < STITCH_HALF_WIDTH {
+                STITCH_COLOR
+            } else {
+                LEATHER_COLOR
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// marks a child entity of the ball (e.g. the one rendering `baseball_mesh`)
+/// whose rotation follows the simulated seam orientation
+#[derive(Debug, Clone, Copy, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct BaseballSeamMesh;
+
+/// orientation of the seams relative to the spin axis
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect, Serialize, Deserialize)]
+pub enum SeamOrientation {
+    /// four seams pass per revolution. symmetric wake
+    #[default]
+    FourSeam,
+    /// two seams pass per revolution
+    TwoSeam,
+    /// seam shifted sinker. a smooth hemisphere produces arm side and downward wake for sinker spin
+    OneSeam,
+    /// seam shifted wake adds glove side movement for sweeper spin
+    Sweeper,
+    /// asymmetric orientation for low spin pitches
+    Knuckleball,
+    /// raw seam angles in rad
+    Custom { y_angle: f32, z_angle: f32 },
+}
+
+impl SeamOrientation {
+    /// seam angles (y, z) in rad used to rotate the seams around the spin axis
+    pub fn angles(&self) -> (f32, f32) {
+        match *self {
+            Self::FourSeam => (0., PI_32 / 2.),
+            Self::TwoSeam => (0., 0.),
+            Self::OneSeam => (PI_32 / 2., 3. * PI_32 / 8.),
+            Self::Sweeper => (3. * PI_32 / 8., PI_32 / 4.),
+            Self::Knuckleball => (PI_32 / 4., PI_32 / 8.),
+            Self::Custom { y_angle, z_angle } => (y_angle, z_angle),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pitch::PitchRelease;
+
+    // field position at the end of the flight with ssw on minus ssw off
+    fn ssw_shift(tilt: (i8, i8), spin_efficiency: f32, orientation: SeamOrientation) -> Vec3 {
+        let release = PitchRelease::builder()
+            .velocity(92.)
+            .spin_rate(2200.)
+            .tilt(Tilt::from_hour_mintes(tilt.0, tilt.1).unwrap())
+            .spin_efficiency(spin_efficiency)
+            .seam_orientation(orientation)
+            .build()
+            .unwrap();
+        let end_position = |ssw_on: bool| {
+            let mut config = SimulationConfig::default();
+            config.aerodynamics.ssw_on = ssw_on;
+            let trajectory =
+                simulate_pitch(&release.to_pitch_params(&config.field_frame), &config).unwrap();
+            config
+                .field_frame
+                .to_field(trajectory.last().unwrap().position)
+        };
+        end_position(true) - end_position(false)
+    }
+
+    #[test]
+    fn one_seam_sinker_moves_arm_side_and_down() {
+        for tilt in [(1, 30), (2, 0), (2, 15)] {
+            let shift = ssw_shift(tilt, 0.9, SeamOrientation::OneSeam);
+            assert!(shift.x < 0. && shift.z < 0., "{tilt:?} {shift}");
+        }
+    }
+
+    #[test]
+    fn sweeper_moves_glove_side() {
+        for tilt in [(8, 30), (9, 0), (9, 30)] {
+            let shift = ssw_shift(tilt, 0.5, SeamOrientation::Sweeper);
+            assert!(shift.x > 0., "{tilt:?} {shift}");
+        }
+    }
+
+    #[test]
+    fn four_seam_has_no_lateral_shift() {
+        let shift = ssw_shift((12, 30), 1., SeamOrientation::FourSeam);
+        assert!(shift.x.abs() < 0.05, "{shift}");
+    }
+}
@@ -0,0 +1,114 @@
+use crate::*;
+
+/// initial conditions of a pitch in the bevy world
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PitchParams {
+    pub position: Vec3,
+    // in m/s
+    pub velocity: Vec3,
+    // in rad/s
+    pub spin: Vec3,
+    pub seam_orientation: SeamOrientation,
+}
+
+/// environment and stop conditions for simulating pitches without a bevy world
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub aerodynamics: BaseballPluginConfig,
+    pub atmosphere: Atmosphere,
+    pub wind: Wind,
+    pub ball: BallProperties,
+    pub field_frame: FieldFrame,
+    // gravitational acceleration in the bevy world
+    pub gravity: Vec3,
+    // time between trajectory samples in s
+    pub sample_interval: f64,
+    // in s
+    pub max_time: f64,
+    // the simulation stops once the ball passes this distance from home plate (field y) in m
+    pub end_plane_y: Option<f32>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            aerodynamics: BaseballPluginConfig::default(),
+            atmosphere: Atmosphere::default(),
+            wind: Wind::default(),
+            ball: BallProperties::default(),
+            field_frame: FieldFrame::default(),
+            gravity: Vec3::NEG_Y * 9.81,
+            sample_interval: 1. / 240.,
+            max_time: 2.,
+            end_plane_y: Some(0.),
+        }
+    }
+}
+
+/// simulates a pitch with the same force model as the plugin, without ECS or rapier.
+///
+/// the simulation ends at `max_time`, once the ball passes `end_plane_y`
+/// or when it hits the ground (field z = 0).
+/// `sample_interval` and `max_time` should be positive
+pub fn simulate_pitch(params: &PitchParams, config: &SimulationConfig) -> Result<Trajectory> {
+    if !config.sample_interval.is_finite() || config.sample_interval <= 0. {
+        return Err(BaseballFlightError::InvalidInput(
+            "sample interval should be positive".into(),
+        ));
+    }
+    if !config.max_time.is_finite() || config.max_time <= 0. {
+        return Err(BaseballFlightError::InvalidInput(
+            "max time should be positive".into(),
+        ));
+    }
+
+    let aerodynamics = &config.aerodynamics;
+    let air_density = config.atmosphere.air_density() as f64;
+    let dynamic_viscosity = config.atmosphere.dynamic_viscosity() as f64;
+    let field_frame = config.field_frame;
+
+    let mut state = BaseballFlightState::from_params(
+        field_frame,
+        params.position.as_dvec3(),
+        params.velocity.as_dvec3(),
+        params.spin.as_dvec3(),
+        params.seam_orientation,
+        vec![],
+        (0., 0.),
+        config.ball,
+    );
+
+    let mut trajectory = Trajectory::default();
+    trajectory.push(TrajectorySample::from_state(&state));
+
+    while config.max_time - state.time_elapsed > f64::EPSILON {
+        let conditions = FlightConditions {
+            air_density,
+            dynamic_viscosity,
+            wind: config
+                .wind
+                .sample(
+                    state.translation.as_vec3(),
+                    state.time_elapsed as f32,
+                    &field_frame,
+                )
+                .as_dvec3(),
+            gravity: config.gravity.as_dvec3(),
+        };
+        let delta_t = config
+            .sample_interval
+            .min(config.max_time - state.time_elapsed);
+        state.update_state(aerodynamics, &conditions, &aerodynamics.integrator, delta_t);
+        trajectory.push(TrajectorySample::from_state(&state));
+
+        let field_translation = field_frame.to_field_d(state.translation);
+        let passed_end_plane = config
+            .end_plane_y
+            .is_some_and(|end_plane_y| field_translation.y < end_plane_y as f64);
+        if passed_end_plane || field_translation.z < 0. {
+            break;
+        }
+    }
+
+    Ok(trajectory)
+}
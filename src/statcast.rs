@@ -0,0 +1,377 @@
+use crate::*;
+
+use crate::pitch::PitchRelease;
+
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+
+// gravitational acceleration in ft/s^2 assumed by the pitch tracking fits
+const GRAVITY_FTS2: f32 = 32.174;
+// the velocities and accelerations of the nine parameter fit are given at this distance from home plate in ft
+pub const STATCAST_Y0: f32 = 50.;
+
+/// a Statcast (or PITCHf/x) pitch with the column names of baseball savant.
+/// units and coordinates are the published ones: feet, ft/s and ft/s^2 in field coordinates
+/// (origin at the point of home plate, +x towards the catcher's right, +y towards the mound, +z up),
+/// which only differ from the plugin's field coordinates in the unit.
+/// velocities and accelerations are given at `STATCAST_Y0`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatcastPitch {
+    pub release_pos_x: f32,
+    pub release_pos_y: f32,
+    pub release_pos_z: f32,
+    pub vx0: f32,
+    pub vy0: f32,
+    pub vz0: f32,
+    pub ax: f32,
+    pub ay: f32,
+    pub az: f32,
+    // in rpm
+    pub release_spin_rate: Option<f32>,
+    // in degrees, 180 is pure backspin
+    pub spin_axis: Option<f32>,
+    // "R" or "L"
+    pub p_throws: Option<String>,
+    // e.g. "FF", "SI", "ST"
+    pub pitch_type: Option<String>,
+}
+
+impl StatcastPitch {
+    /// reads a baseball savant csv export. unknown columns are ignored
+    pub fn read_csv<R: Read>(reader: R) -> Result<Vec<Self>> {
+        csv::Reader::from_reader(reader)
+            .deserialize()
+            .map(|record| record.map_err(|e| BaseballFlightError::ImportError(e.to_string())))
+            .collect()
+    }
+
+    /// release point in the plugin's field coordinates (m)
+    pub fn release_point(&self) -> Vec3 {
+        Vec3::new(self.release_pos_x, self.release_pos_y, self.release_pos_z) / M_TO_FEET
+    }
+
+    /// velocity at the release point in ft/s, extrapolated from the nine parameter fit
+    pub fn release_velocity(&self) -> Result<Vec3> {
+        let time = self.time_from_y0(self.release_pos_y)?;
+        Ok(Vec3::new(self.vx0, self.vy0, self.vz0) + self.acceleration() * time)
+    }
+
+    /// acceleration of the nine parameter fit in ft/s^2
+    pub fn acceleration(&self) -> Vec3 {
+        Vec3::new(self.ax, self.ay, self.az)
+    }
+
+    /// spin efficiency estimated from the magnus acceleration of the fit, using the default lift model
+    pub fn spin_efficiency(&self) -> Result<f32> {
+        self.spin_efficiency_with(
+            &LiftModelKind::default(),
+            &Atmosphere::default(),
+            &BallProperties::default(),
+        )
+    }
+
+    /// spin efficiency estimated from the magnus acceleration of the fit.
+    /// the magnus acceleration of the plugin is `c_0 v^2 cl(s) e` with the spin factor `s` of the whole spin,
+    /// which is solved for the efficiency `e`.
+    /// the seam shifted wake is not separated from the magnus acceleration, so the pitch should be replayed
+    /// with `ssw_on = false` to reproduce the acceleration of the fit
+    pub fn spin_efficiency_with(
+        &self,
+        lift_model: &LiftModelKind,
+        atmosphere: &Atmosphere,
+        ball: &BallProperties,
+    ) -> Result<f32> {
+        let spin_rate = self.release_spin_rate.unwrap_or_default() as f64 * RPM_TO_RADS as f64;
+        if spin_rate <= 0. {
+            return Ok(1.);
+        }
+        // the fit averages the acceleration over the flight, so it is matched halfway to home plate
+        let time = 0.5
+            * (self.time_from_y0(self.release_pos_y)?
+                + self.time_from_y0(PLATE_FRONT * M_TO_FEET)?);
+        let velocity = ((Vec3::new(self.vx0, self.vy0, self.vz0) + self.acceleration() * time)
+            / M_TO_FEET)
+            .as_dvec3();
+        let speed = velocity.length();
+        // everything but gravity and the component along the velocity (drag) is magnus
+        let aero = ((self.acceleration() + Vec3::Z * GRAVITY_FTS2) / M_TO_FEET).as_dvec3();
+        let magnus = aero - velocity * (aero.dot(velocity) / speed.powi(2));
+
+        let air_density = atmosphere.air_density() as f64;
+        let radius = ball.radius as f64;
+        let lift_coefficient = lift_model.lift_coefficient(&FlowConditions {
+            speed,
+            reynolds_number: air_density * speed * 2. * radius
+                / atmosphere.dynamic_viscosity() as f64,
+            spin_rate,
+            spin_factor: radius * spin_rate / speed,
+        });
+        let efficiency =
+            magnus.length() / (ball.c_0(air_density) * speed.powi(2) * lift_coefficient);
+        Ok(if efficiency.is_finite() {
+            efficiency.clamp(0., 1.) as f32
+        } else {
+            1.
+        })
+    }
+
+    pub fn tilt(&self) -> Tilt {
+        self.spin_axis.map(Tilt::from_spin_axis).unwrap_or_default()
+    }
+
+    /// the gyro pole of a left handed pitcher mirrors the one of a right handed pitcher
+    pub fn gyro_pole(&self) -> GyroPole {
+        match self.p_throws.as_deref() {
+            Some("L") => GyroPole::Left,
+            _ => GyroPole::Right,
+        }
+    }
+
+    /// seam orientation preset matching the pitch type
+    pub fn seam_orientation(&self) -> SeamOrientation {
+        match self.pitch_type.as_deref() {
+            Some("FT") => SeamOrientation::TwoSeam,
+            Some("SI") => SeamOrientation::OneSeam,
+            Some("ST") => SeamOrientation::Sweeper,
+            Some("KN") => SeamOrientation::Knuckleball,
+            _ => SeamOrientation::FourSeam,
+        }
+    }
+
+    /// release parameters for `spawn_pitch` and `PitchRelease::to_pitch_params`
+    pub fn to_release(&self) -> Result<PitchRelease> {
+        let velocity = self.release_velocity()?;
+        let speed = velocity.length();
+        PitchRelease::builder()
+            .release_point(self.release_point())
+            .velocity(speed / MPH_TO_FTS)
+            .release_angles(
+                (velocity.z / speed).asin().to_degrees(),
+                velocity.x.atan2(-velocity.y).to_degrees(),
+            )
+            .spin_rate(self.release_spin_rate.unwrap_or_default())
+            .tilt(self.tilt())
+            .spin_efficiency(self.spin_efficiency()?)
+            .gyro_pole(self.gyro_pole())
+            .seam_orientation(self.seam_orientation())
+            .build()
+    }
+
+    // time in s at which the fit passes `y` (ft). negative before `STATCAST_Y0`
+    fn time_from_y0(&self, y: f32) -> Result<f32> {
+        let (a, b, c) = (0.5 * self.ay, self.vy0, STATCAST_Y0 - y);
+        if a.abs() < f32::EPSILON {
+            if b.abs() < f32::EPSILON {
+                return Err(BaseballFlightError::InvalidInput(
+                    "vy0 should not be zero".into(),
+                ));
+            }
+            return Ok(-c / b);
+        }
+        let discriminant = b.powi(2) - 4. * a * c;
+        if discriminant < 0. {
+            return Err(BaseballFlightError::InvalidInput(
+                "the pitch never reaches the release point".into(),
+            ));
+        }
+        let roots = [
+            (-b + discriminant.sqrt()) / (2. * a),
+            (-b - discriminant.sqrt()) / (2. * a),
+        ];
+        Ok(if roots[0].abs() < roots[1].abs() {
+            roots[0]
+        } else {
+            roots[1]
+        })
+    }
+}
+
+/// PITCHf/x style constant acceleration fit of a flight, in feet, ft/s and ft/s^2 in field coordinates.
+/// position and velocity are given at `y0`, which is `STATCAST_Y0` unless the ball never got there
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct NineParameterFit {
+    pub x0: f32,
+    pub y0: f32,
+    pub z0: f32,
+    pub vx0: f32,
+    pub vy0: f32,
+    pub vz0: f32,
+    pub ax: f32,
+    pub ay: f32,
+    pub az: f32,
+}
+
+impl NineParameterFit {
+    /// least squares fit of the samples between the release and the front of home plate
+    pub fn from_trajectory(trajectory: &Trajectory, field_frame: &FieldFrame) -> Result<Self> {
+        let samples = trajectory
+            .samples()
+            .iter()
+            .map(|sample| {
+                (
+                    sample.time,
+                    field_frame.to_field(sample.position) * M_TO_FEET,
+                )
+            })
+            .filter(|(_, position)| position.y >= PLATE_FRONT * M_TO_FEET)
+            .collect::<Vec<_>>();
+        if samples.len() < 3 {
+            return Err(BaseballFlightError::InvalidInput(
+                "at least three samples before home plate are needed".into(),
+            ));
+        }
+
+        // times relative to the first sample keep the normal equations well conditioned
+        let t_first = samples[0].0;
+        let (mut normal, mut rhs) = (DMat3::ZERO, [DVec3::ZERO; 3]);
+        for (time, position) in &samples {
+            let t = time - t_first;
+            let basis = DVec3::new(1., t, t * t);
+            normal += DMat3::from_cols(basis * basis.x, basis * basis.y, basis * basis.z);
+            for (axis, rhs) in rhs.iter_mut().enumerate() {
+                *rhs += basis * position[axis] as f64;
+            }
+        }
+        if normal.determinant().abs() < f64::EPSILON {
+            return Err(BaseballFlightError::InvalidInput(
+                "samples should be at distinct times".into(),
+            ));
+        }
+        let inverse = normal.inverse();
+        // (constant, linear, quadratic) coefficients per axis
+        let [x, y, z] = rhs.map(|rhs| inverse * rhs);
+
+        // reference time at which the fit passes y0
+        let y0 = (STATCAST_Y0 as f64).min(y.x);
+        let (a, b, c) = (y.z, y.y, y.x - y0);
+        let t0 = if a.abs() < f64::EPSILON {
+            -c / b
+        } else {
+            let discriminant = (b * b - 4. * a * c).max(0.);
+            let roots = [
+                (-b + discriminant.sqrt()) / (2. * a),
+                (-b - discriminant.sqrt()) / (2. * a),
+            ];
+            // the first crossing after the first sample
+            roots
+                .into_iter()
+                .filter(|root| *root >= 0.)
+                .fold(f64::INFINITY, f64::min)
+        };
+        if !t0.is_finite() {
+            return Err(BaseballFlightError::InvalidInput(
+                "the trajectory should travel towards home plate".into(),
+            ));
+        }
+
+        let position = |p: DVec3| p.x + p.y * t0 + p.z * t0 * t0;
+        let velocity = |p: DVec3| p.y + 2. * p.z * t0;
+        Ok(Self {
+            x0: position(x) as f32,
+            y0: y0 as f32,
+            z0: position(z) as f32,
+            vx0: velocity(x) as f32,
+            vy0: velocity(y) as f32,
+            vz0: velocity(z) as f32,
+            ax: (2. * x.z) as f32,
+            ay: (2. * y.z) as f32,
+            az: (2. * z.z) as f32,
+        })
+    }
+
+    /// position in ft at `t` s after passing `y0`
+    pub fn position(&self, t: f32) -> Vec3 {
+        self.initial_position() + self.initial_velocity() * t + self.acceleration() * (0.5 * t * t)
+    }
+
+    /// velocity in ft/s at `t` s after passing `y0`
+    pub fn velocity(&self, t: f32) -> Vec3 {
+        self.initial_velocity() + self.acceleration() * t
+    }
+
+    pub fn initial_position(&self) -> Vec3 {
+        Vec3::new(self.x0, self.y0, self.z0)
+    }
+
+    pub fn initial_velocity(&self) -> Vec3 {
+        Vec3::new(self.vx0, self.vy0, self.vz0)
+    }
+
+    pub fn acceleration(&self) -> Vec3 {
+        Vec3::new(self.ax, self.ay, self.az)
+    }
+}
+
+impl Trajectory {
+    /// see `NineParameterFit::from_trajectory`
+    pub fn nine_parameter_fit(&self, field_frame: &FieldFrame) -> Result<NineParameterFit> {
+        NineParameterFit::from_trajectory(self, field_frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn four_seam() -> StatcastPitch {
+        StatcastPitch {
+            release_pos_x: -1.9,
+            release_pos_y: 54.,
+            release_pos_z: 5.9,
+            vx0: 6.2,
+            vy0: -138.3,
+            vz0: -5.9,
+            ax: -10.1,
+            ay: 30.2,
+            az: -13.8,
+            release_spin_rate: Some(2350.),
+            spin_axis: Some(212.),
+            p_throws: Some("R".into()),
+            pitch_type: Some("FF".into()),
+        }
+    }
+
+    #[test]
+    fn replay_matches_the_magnus_acceleration() {
+        let pitch = four_seam();
+        let release = pitch.to_release().unwrap();
+        let mut config = SimulationConfig::default();
+        config.aerodynamics.ssw_on = false;
+        let trajectory =
+            simulate_pitch(&release.to_pitch_params(&config.field_frame), &config).unwrap();
+
+        // halfway between the release and the front of home plate
+        let time = 0.5
+            * (pitch.time_from_y0(PLATE_FRONT * M_TO_FEET).unwrap()
+                - pitch.time_from_y0(pitch.release_pos_y).unwrap()) as f64;
+        let sample = trajectory
+            .samples()
+            .iter()
+            .min_by(|a, b| (a.time - time).abs().total_cmp(&(b.time - time).abs()))
+            .unwrap();
+        let velocity = config.field_frame.direction_to_field(sample.velocity);
+        let aero = pitch.acceleration() + Vec3::Z * GRAVITY_FTS2;
+        let magnus = aero - velocity * (aero.dot(velocity) / velocity.length_squared());
+        let simulated = sample.accelerations.magnus.length() * M_TO_FEET;
+        assert!(
+            (simulated - magnus.length()).abs() < 0.5,
+            "{simulated} and {}",
+            magnus.length()
+        );
+    }
+
+    #[test]
+    fn replay_reproduces_the_fit() {
+        let pitch = four_seam();
+        let release = pitch.to_release().unwrap();
+        let mut config = SimulationConfig::default();
+        config.aerodynamics.ssw_on = false;
+        let fit = simulate_pitch(&release.to_pitch_params(&config.field_frame), &config)
+            .unwrap()
+            .nine_parameter_fit(&config.field_frame)
+            .unwrap();
+        assert!((fit.ax - pitch.ax).abs() < 0.5, "{fit:?}");
+        assert!((fit.az - pitch.az).abs() < 0.5, "{fit:?}");
+    }
+}
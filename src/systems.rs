@@ -1,40 +1,92 @@
 use crate::*;
 
+// optional per ball components taking precedence over the global configuration
+type BallOverrides<'a> = (
+    Option<&'a FlightIntegrator>,
+    Option<&'a SeamShiftedWake>,
+    Option<&'a AeroOverrides>,
+);
+
 // option 1 - update transform
 pub(crate) fn _apply_physics_option_1(
-    time_fixed: Res<Time<Fixed>>,
     rapier_config: Res<RapierConfiguration>,
     baseball_plugin_config: Res<BaseballPluginConfig>,
-    mut query_baseball: Query<(&mut BaseballFlightState, &mut Transform)>,
+    time: Res<Time>,
+    atmosphere: Res<Atmosphere>,
+    wind: Res<Wind>,
+    field_frame: Res<FieldFrame>,
+    mut query_baseball: Query<(&mut BaseballFlightState, &mut Transform, BallOverrides)>,
 ) {
-    let delta_t = get_delta_t(&time_fixed, &rapier_config);
-    for (mut state, mut transform) in &mut query_baseball {
+    let delta_t = get_delta_t(&time, &rapier_config);
+    let air_density = atmosphere.air_density() as f64;
+    let dynamic_viscosity = atmosphere.dynamic_viscosity() as f64;
+    let gravity = rapier_config.gravity.as_dvec3();
+    for (mut state, mut transform, (integrator, ssw, overrides)) in &mut query_baseball {
         if state.active {
-            state.update_state(&baseball_plugin_config, delta_t);
-            transform.translation = state.translation.as_vec3().from_baseball_coord_to_bevy();
+            let config = baseball_plugin_config.for_ball(ssw, overrides);
+            let integrator = integrator.map_or(config.integrator, |i| i.0);
+            let conditions = FlightConditions {
+                air_density,
+                dynamic_viscosity,
+                wind: wind
+                    .sample(
+                        transform.translation,
+                        state.time_elapsed as f32,
+                        &field_frame,
+                    )
+                    .as_dvec3(),
+                gravity,
+            };
+            state.update_state(&config, &conditions, &integrator, delta_t);
+            transform.translation = state.translation.as_vec3();
         }
     }
 }
 
 // option 2 - update velocity
 pub(crate) fn _apply_physics_option_2(
-    time_fixed: Res<Time<Fixed>>,
     rapier_config: Res<RapierConfiguration>,
     baseball_plugin_config: Res<BaseballPluginConfig>,
-    mut query_baseball: Query<(&mut BaseballFlightState, &Transform, &mut Velocity)>,
+    time: Res<Time>,
+    atmosphere: Res<Atmosphere>,
+    wind: Res<Wind>,
+    field_frame: Res<FieldFrame>,
+    mut query_baseball: Query<(
+        &mut BaseballFlightState,
+        &Transform,
+        &mut Velocity,
+        BallOverrides,
+    )>,
 ) {
-    let delta_t = get_delta_t(&time_fixed, &rapier_config);
-    for (mut state, transform, mut velo) in &mut query_baseball {
+    let delta_t = get_delta_t(&time, &rapier_config);
+    let air_density = atmosphere.air_density() as f64;
+    let dynamic_viscosity = atmosphere.dynamic_viscosity() as f64;
+    let gravity = rapier_config.gravity.as_dvec3();
+    for (mut state, transform, mut velo, (integrator, ssw, overrides)) in &mut query_baseball {
         if state.active {
-            let new_velo = state._update_state_and_get_velo(
-                &baseball_plugin_config,
-                transform
-                    .translation
-                    .from_bevy_to_baseball_coord()
+            let config = baseball_plugin_config.for_ball(ssw, overrides);
+            let integrator = integrator.map_or(config.integrator, |i| i.0);
+            let conditions = FlightConditions {
+                air_density,
+                dynamic_viscosity,
+                wind: wind
+                    .sample(
+                        transform.translation,
+                        state.time_elapsed as f32,
+                        &field_frame,
+                    )
                     .as_dvec3(),
+                gravity,
+            };
+            let new_velo = state._update_state_and_get_velo(
+                &config,
+                &conditions,
+                &integrator,
+                transform.translation.as_dvec3(),
                 delta_t,
             );
-            velo.linvel = new_velo.from_baseball_coord_to_bevy().as_vec3();
+            velo.linvel = new_velo.as_vec3();
+            velo.angvel = state.spin.as_vec3();
         }
     }
 }
@@ -42,29 +94,52 @@ pub(crate) fn _apply_physics_option_2(
 // preferred
 // option 3 - apply external force
 pub(crate) fn _apply_physics_option_3(
-    time_fixed: Res<Time<Fixed>>,
     rapier_config: Res<RapierConfiguration>,
     baseball_plugin_config: Res<BaseballPluginConfig>,
+    time: Res<Time>,
+    atmosphere: Res<Atmosphere>,
+    wind: Res<Wind>,
+    field_frame: Res<FieldFrame>,
     mut query_baseball: Query<(
         &mut BaseballFlightState,
         &Transform,
-        &Velocity,
+        &mut Velocity,
         &mut ExternalForce,
+        BallOverrides,
     )>,
 ) {
-    let delta_t = get_delta_t(&time_fixed, &rapier_config);
-    for (mut state, transform, velo, mut force) in &mut query_baseball {
+    let delta_t = get_delta_t(&time, &rapier_config);
+    let air_density = atmosphere.air_density() as f64;
+    let dynamic_viscosity = atmosphere.dynamic_viscosity() as f64;
+    let gravity = rapier_config.gravity.as_dvec3();
+    for (mut state, transform, mut velo, mut force, (integrator, ssw, overrides)) in
+        &mut query_baseball
+    {
         if state.active {
-            let a = state.update_state_and_get_acceleration(
-                &baseball_plugin_config,
-                transform
-                    .translation
-                    .from_bevy_to_baseball_coord()
+            let config = baseball_plugin_config.for_ball(ssw, overrides);
+            let integrator = integrator.map_or(config.integrator, |i| i.0);
+            let conditions = FlightConditions {
+                air_density,
+                dynamic_viscosity,
+                wind: wind
+                    .sample(
+                        transform.translation,
+                        state.time_elapsed as f32,
+                        &field_frame,
+                    )
                     .as_dvec3(),
-                velo.linvel.from_bevy_to_baseball_coord().as_dvec3(),
+                gravity,
+            };
+            let a = state.update_state_and_get_acceleration(
+                &config,
+                &conditions,
+                &integrator,
+                transform.translation.as_dvec3(),
+                velo.linvel.as_dvec3(),
                 delta_t,
             );
-            force.force = a.from_baseball_coord_to_bevy().as_vec3() * MASS;
+            force.force = a.as_vec3() * state.ball.mass;
+            velo.angvel = state.spin.as_vec3();
             // }
         } else {
             // info!("inactive aerodynamics");
@@ -72,45 +147,156 @@ pub(crate) fn _apply_physics_option_3(
     }
 }
 
-fn get_delta_t(time_fixed: &Res<Time<Fixed>>, rapier_config: &Res<RapierConfiguration>) -> f64 {
-    let delta_t = match rapier_config.timestep_mode {
-        TimestepMode::Variable { time_scale, .. } => {
-            time_scale as f64 * time_fixed.delta_seconds_f64()
+pub(crate) fn update_aero_forces(
+    mut query_baseball: Query<
+        (&BaseballFlightState, &mut AeroForces),
+        Changed<BaseballFlightState>,
+    >,
+) {
+    for (state, mut forces) in &mut query_baseball {
+        if state.active {
+            *forces = AeroForces::from_state(state);
+        }
+    }
+}
+
+// passes the samples of this frame's steps to the recorder and completes the recording once the ball is deactivated
+pub(crate) fn record_trajectories(
+    mut query_baseball: Query<(Entity, &mut BaseballFlightState, &mut TrajectoryRecorder)>,
+    mut ev_trajectory_recorded_event: EventWriter<TrajectoryRecordedEvent>,
+) {
+    for (entity, mut state, mut recorder) in &mut query_baseball {
+        if !state.step_samples.is_empty() {
+            for sample in state.step_samples.drain(..) {
+                recorder.record(sample);
+            }
+        }
+        if !state.active {
+            if let Some(trajectory) = recorder.finish() {
+                ev_trajectory_recorded_event.send(TrajectoryRecordedEvent { entity, trajectory });
+            }
+        }
+    }
+}
+
+pub(crate) fn send_plate_crossed_events(
+    mut query_baseball: Query<(Entity, &mut BaseballFlightState)>,
+    mut ev_plate_crossed_event: EventWriter<PlateCrossedEvent>,
+) {
+    for (entity, mut state) in &mut query_baseball {
+        if state.plate_crossings.is_empty() {
+            continue;
+        }
+        for crossing in state.plate_crossings.drain(..) {
+            ev_plate_crossed_event.send(crossing.to_event(entity));
+        }
+    }
+}
+
+// measures every recorded flight once it is complete
+pub(crate) fn compute_pitch_metrics(
+    mut commands: Commands,
+    rapier_config: Res<RapierConfiguration>,
+    field_frame: Res<FieldFrame>,
+    mut ev_trajectory_recorded_event: EventReader<TrajectoryRecordedEvent>,
+) {
+    for ev in ev_trajectory_recorded_event.read() {
+        match PitchMetrics::from_trajectory(&ev.trajectory, &field_frame, rapier_config.gravity) {
+            Ok(metrics) => {
+                if let Some(mut entity) = commands.get_entity(ev.entity) {
+                    entity.insert(metrics);
+                }
+            }
+            Err(e) => info!("no pitch metrics for {:?}: {}", ev.entity, e),
+        }
+    }
+}
+
+// the ball's rigid body starts unrotated, so the seam orientation is applied relative to it
+pub(crate) fn sync_seam_mesh(
+    query_baseball: Query<(&BaseballFlightState, &Transform), Without<BaseballSeamMesh>>,
+    mut query_mesh: Query<(&Parent, &mut Transform), With<BaseballSeamMesh>>,
+) {
+    for (parent, mut transform) in &mut query_mesh {
+        if let Ok((state, parent_transform)) = query_baseball.get(parent.get()) {
+            if state.active {
+                transform.rotation = parent_transform.rotation.inverse() * state.get_orientation();
+            }
         }
+    }
+}
+
+// the time step rapier integrates the current frame with
+fn get_delta_t(time: &Res<Time>, rapier_config: &Res<RapierConfiguration>) -> f64 {
+    match rapier_config.timestep_mode {
+        TimestepMode::Variable {
+            max_dt, time_scale, ..
+        } => (time_scale as f64 * time.delta_seconds_f64()).min(max_dt as f64),
         TimestepMode::Fixed { dt, .. } => dt as f64,
         TimestepMode::Interpolated { dt, time_scale, .. } => time_scale as f64 * dt as f64,
+    }
+}
+
+// copies the config asset into the resource once it is loaded and whenever it is modified
+pub(crate) fn sync_config_asset(
+    mut asset_events: EventReader<AssetEvent<BaseballPluginConfig>>,
+    handle: Option<Res<BaseballPluginConfigHandle>>,
+    assets: Res<Assets<BaseballPluginConfig>>,
+    mut baseball_plugin_config: ResMut<BaseballPluginConfig>,
+) {
+    let Some(handle) = handle else {
+        asset_events.clear();
+        return;
     };
-    (delta_t * 1000.).floor() / 1000.
+    let updated = asset_events
+        .read()
+        .any(|event| event.is_loaded_with_dependencies(&handle.0) || event.is_modified(&handle.0));
+    if updated || handle.is_changed() {
+        if let Some(config) = assets.get(&handle.0) {
+            *baseball_plugin_config = config.clone();
+        }
+    }
 }
 
 pub(crate) fn activate_aerodynamics(
-    mut ball_physics_query: Query<(&mut BaseballFlightState, &Transform, &Velocity)>,
+    field_frame: Res<FieldFrame>,
+    mut ball_physics_query: Query<(
+        &mut BaseballFlightState,
+        &Transform,
+        &Velocity,
+        Option<&BallProperties>,
+    )>,
+    mut query_recorder: Query<&mut TrajectoryRecorder>,
     mut ev_activate_aerodynamics_event: EventReader<ActivateAerodynamicsEvent>,
     mut ev_post_activate_aerodynamics_event: EventWriter<PostActivateAerodynamicsEvent>,
 ) {
     for ev in ev_activate_aerodynamics_event.read() {
         info!("activate aerodynamics {:?}", ev.entity);
-        if let Ok((mut state, transform, velo)) = ball_physics_query.get_mut(ev.entity) {
+        if let Ok((mut state, transform, velo, properties)) = ball_physics_query.get_mut(ev.entity)
+        {
             info!("query aerodynamics");
             if !state.active {
                 // just in case
                 // force.force = Vec3::ZERO;
                 //
                 *state = BaseballFlightState::from_params(
-                    transform
-                        .translation
-                        .from_bevy_to_baseball_coord()
-                        .as_dvec3(),
-                    velo.linvel.from_bevy_to_baseball_coord().as_dvec3(),
-                    velo.angvel.from_bevy_to_baseball_coord().as_dvec3(),
-                    ev.seam_y_angle,
-                    ev.seam_z_angle,
+                    *field_frame,
+                    transform.translation.as_dvec3(),
+                    velo.linvel.as_dvec3(),
+                    velo.angvel.as_dvec3(),
+                    ev.seam_orientation,
                     ev.record_times.clone(),
                     //
-                    |(front_z, back_z)| -> (f64, f64) {
-                        ((front_z * M_TO_FEET) as f64, (back_z * M_TO_FEET) as f64)
-                    }(ev.strikezone_panels_z),
+                    (
+                        ev.strikezone_panels_z.0 as f64,
+                        ev.strikezone_panels_z.1 as f64,
+                    ),
+                    properties.copied().unwrap_or_default(),
                 );
+                if let Ok(mut recorder) = query_recorder.get_mut(ev.entity) {
+                    state.record_steps = true;
+                    recorder.start(TrajectorySample::from_state(&state));
+                }
                 //
                 ev_post_activate_aerodynamics_event.send(PostActivateAerodynamicsEvent(ev.entity));
             }
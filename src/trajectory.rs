@@ -0,0 +1,517 @@
+use crate::*;
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// state of the ball at a point in time. all vectors are in the bevy world.
+/// serialized as a flat row, see `TRAJECTORY_COLUMNS`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(into = "TrajectoryRow", from = "TrajectoryRow")]
+pub struct TrajectorySample {
+    // time since release in s
+    pub time: f64,
+    pub position: Vec3,
+    pub velocity: Vec3,
+    // in rad/s
+    pub spin: Vec3,
+    // accelerations of each force over the last integrator step in m/s^2. zero for the release sample
+    pub accelerations: ForceComponents,
+}
+
+impl TrajectorySample {
+    pub(crate) fn from_state(state: &BaseballFlightState) -> Self {
+        Self {
+            time: state.time_elapsed,
+            position: state.translation.as_vec3(),
+            velocity: state.v.as_vec3(),
+            spin: state.spin.as_vec3(),
+            accelerations: ForceComponents::from_breakdown(&state.accelerations, 1.),
+        }
+    }
+}
+
+/// column names of the csv and json lines exports, one row per sample
+pub const TRAJECTORY_COLUMNS: [&str; 22] = [
+    "t",
+    "x",
+    "y",
+    "z",
+    "vx",
+    "vy",
+    "vz",
+    "spin_x",
+    "spin_y",
+    "spin_z",
+    "drag_x",
+    "drag_y",
+    "drag_z",
+    "magnus_x",
+    "magnus_y",
+    "magnus_z",
+    "ssw_x",
+    "ssw_y",
+    "ssw_z",
+    "gravity_x",
+    "gravity_y",
+    "gravity_z",
+];
+
+// flat serde representation of `TrajectorySample`. field names follow `TRAJECTORY_COLUMNS`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct TrajectoryRow {
+    t: f64,
+    x: f32,
+    y: f32,
+    z: f32,
+    vx: f32,
+    vy: f32,
+    vz: f32,
+    spin_x: f32,
+    spin_y: f32,
+    spin_z: f32,
+    drag_x: f32,
+    drag_y: f32,
+    drag_z: f32,
+    magnus_x: f32,
+    magnus_y: f32,
+    magnus_z: f32,
+    ssw_x: f32,
+    ssw_y: f32,
+    ssw_z: f32,
+    gravity_x: f32,
+    gravity_y: f32,
+    gravity_z: f32,
+}
+
+impl From<TrajectorySample> for TrajectoryRow {
+    fn from(sample: TrajectorySample) -> Self {
+        let TrajectorySample {
+            time,
+            position,
+            velocity,
+            spin,
+            accelerations,
+        } = sample;
+        Self {
+            t: time,
+            x: position.x,
+            y: position.y,
+            z: position.z,
+            vx: velocity.x,
+            vy: velocity.y,
+            vz: velocity.z,
+            spin_x: spin.x,
+            spin_y: spin.y,
+            spin_z: spin.z,
+            drag_x: accelerations.drag.x,
+            drag_y: accelerations.drag.y,
+            drag_z: accelerations.drag.z,
+            magnus_x: accelerations.magnus.x,
+            magnus_y: accelerations.magnus.y,
+            magnus_z: accelerations.magnus.z,
+            ssw_x: accelerations.ssw.x,
+            ssw_y: accelerations.ssw.y,
+            ssw_z: accelerations.ssw.z,
+            gravity_x: accelerations.gravity.x,
+            gravity_y: accelerations.gravity.y,
+            gravity_z: accelerations.gravity.z,
+        }
+    }
+}
+
+impl From<TrajectoryRow> for TrajectorySample {
+    fn from(row: TrajectoryRow) -> Self {
+        Self {
+            time: row.t,
+            position: Vec3::new(row.x, row.y, row.z),
+            velocity: Vec3::new(row.vx, row.vy, row.vz),
+            spin: Vec3::new(row.spin_x, row.spin_y, row.spin_z),
+            accelerations: ForceComponents {
+                drag: Vec3::new(row.drag_x, row.drag_y, row.drag_z),
+                magnus: Vec3::new(row.magnus_x, row.magnus_y, row.magnus_z),
+                ssw: Vec3::new(row.ssw_x, row.ssw_y, row.ssw_z),
+                gravity: Vec3::new(row.gravity_x, row.gravity_y, row.gravity_z),
+            },
+        }
+    }
+}
+
+/// sampled flight of a ball
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Trajectory {
+    pub samples: Vec<TrajectorySample>,
+}
+
+impl Trajectory {
+    pub fn samples(&self) -> &[TrajectorySample] {
+        &self.samples
+    }
+
+    pub fn first(&self) -> Option<&TrajectorySample> {
+        self.samples.first()
+    }
+
+    pub fn last(&self) -> Option<&TrajectorySample> {
+        self.samples.last()
+    }
+
+    /// flight time in s
+    pub fn duration(&self) -> f64 {
+        match (self.first(), self.last()) {
+            (Some(first), Some(last)) => last.time - first.time,
+            _ => 0.,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub(crate) fn push(&mut self, sample: TrajectorySample) {
+        self.samples.push(sample);
+    }
+
+    /// the same trajectory with all vectors in field coordinates
+    pub fn to_field(&self, field_frame: &FieldFrame) -> Self {
+        let samples = self
+            .samples
+            .iter()
+            .map(|sample| TrajectorySample {
+                position: field_frame.to_field(sample.position),
+                velocity: field_frame.direction_to_field(sample.velocity),
+                spin: field_frame.direction_to_field(sample.spin),
+                accelerations: sample
+                    .accelerations
+                    .map(|vec| field_frame.direction_to_field(vec)),
+                ..*sample
+            })
+            .collect();
+        Self { samples }
+    }
+
+    /// one column per sample field, see `TRAJECTORY_COLUMNS`
+    pub fn to_columns(&self) -> TrajectoryColumns {
+        TrajectoryColumns::from(self)
+    }
+
+    /// writes a header and one row per sample. the header is written for empty trajectories too
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<()> {
+        let mut csv_writer = csv::WriterBuilder::new()
+            .has_headers(false)
+            .from_writer(writer);
+        csv_writer
+            .write_record(TRAJECTORY_COLUMNS)
+            .map_err(|e| BaseballFlightError::ExportError(e.to_string()))?;
+        for sample in &self.samples {
+            csv_writer
+                .serialize(sample)
+                .map_err(|e| BaseballFlightError::ExportError(e.to_string()))?;
+        }
+        csv_writer
+            .flush()
+            .map_err(|e| BaseballFlightError::ExportError(e.to_string()))
+    }
+
+    /// writes one json object per sample and line
+    pub fn write_json_lines<W: Write>(&self, mut writer: W) -> Result<()> {
+        for sample in &self.samples {
+            serde_json::to_writer(&mut writer, sample)
+                .map_err(|e| BaseballFlightError::ExportError(e.to_string()))?;
+            writer
+                .write_all(b"\n")
+                .map_err(|e| BaseballFlightError::ExportError(e.to_string()))?;
+        }
+        writer
+            .flush()
+            .map_err(|e| BaseballFlightError::ExportError(e.to_string()))
+    }
+}
+
+/// columnar layout of a `Trajectory`, e.g. for dataframes. serialize it with any serde format
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrajectoryColumns {
+    pub t: Vec<f64>,
+    pub x: Vec<f32>,
+    pub y: Vec<f32>,
+    pub z: Vec<f32>,
+    pub vx: Vec<f32>,
+    pub vy: Vec<f32>,
+    pub vz: Vec<f32>,
+    pub spin_x: Vec<f32>,
+    pub spin_y: Vec<f32>,
+    pub spin_z: Vec<f32>,
+    pub drag_x: Vec<f32>,
+    pub drag_y: Vec<f32>,
+    pub drag_z: Vec<f32>,
+    pub magnus_x: Vec<f32>,
+    pub magnus_y: Vec<f32>,
+    pub magnus_z: Vec<f32>,
+    pub ssw_x: Vec<f32>,
+    pub ssw_y: Vec<f32>,
+    pub ssw_z: Vec<f32>,
+    pub gravity_x: Vec<f32>,
+    pub gravity_y: Vec<f32>,
+    pub gravity_z: Vec<f32>,
+}
+
+impl From<&Trajectory> for TrajectoryColumns {
+    fn from(trajectory: &Trajectory) -> Self {
+        let mut columns = Self::default();
+        for &sample in trajectory.samples() {
+            let row = TrajectoryRow::from(sample);
+            columns.t.push(row.t);
+            columns.x.push(row.x);
+            columns.y.push(row.y);
+            columns.z.push(row.z);
+            columns.vx.push(row.vx);
+            columns.vy.push(row.vy);
+            columns.vz.push(row.vz);
+            columns.spin_x.push(row.spin_x);
+            columns.spin_y.push(row.spin_y);
+            columns.spin_z.push(row.spin_z);
+            columns.drag_x.push(row.drag_x);
+            columns.drag_y.push(row.drag_y);
+            columns.drag_z.push(row.drag_z);
+            columns.magnus_x.push(row.magnus_x);
+            columns.magnus_y.push(row.magnus_y);
+            columns.magnus_z.push(row.magnus_z);
+            columns.ssw_x.push(row.ssw_x);
+            columns.ssw_y.push(row.ssw_y);
+            columns.ssw_z.push(row.ssw_z);
+            columns.gravity_x.push(row.gravity_x);
+            columns.gravity_y.push(row.gravity_y);
+            columns.gravity_z.push(row.gravity_z);
+        }
+        columns
+    }
+}
+
+/// how often `TrajectoryRecorder` samples the flight
+#[derive(Debug, Clone, Copy, Reflect, PartialEq)]
+pub enum RecordRate {
+    /// every integrator step, including the partial step at the end of a frame
+    EveryStep,
+    /// samples per second. each sample is the first integrator step at or after the sample time.
+    /// rates that are not positive and finite record every step
+    Hz(f64),
+}
+
+impl Default for RecordRate {
+    fn default() -> Self {
+        Self::Hz(240.)
+    }
+}
+
+impl RecordRate {
+    // time between samples in s. `None` records every step
+    fn interval(&self) -> Option<f64> {
+        match *self {
+            Self::Hz(hz) if hz > 0. && hz.is_finite() => Some(1. / hz),
+            _ => None,
+        }
+    }
+}
+
+/// records the flight of the ball from activation until it is deactivated.
+/// `TrajectoryRecordedEvent` is sent with the complete trajectory once the ball is deactivated
+#[derive(Debug, Clone, Default, Component)]
+pub struct TrajectoryRecorder {
+    pub rate: RecordRate,
+    trajectory: Trajectory,
+    // time since release of the next sample in s
+    next_sample_time: f64,
+    recording: bool,
+}
+
+impl TrajectoryRecorder {
+    pub fn new(rate: RecordRate) -> Self {
+        Self {
+            rate,
+            ..Default::default()
+        }
+    }
+
+    pub fn every_step() -> Self {
+        Self::new(RecordRate::EveryStep)
+    }
+
+    /// `hz` samples per second. `hz` should be positive
+    pub fn with_rate(hz: f64) -> Result<Self> {
+        if !hz.is_finite() || hz <= 0. {
+            return Err(BaseballFlightError::InvalidInput(
+                "record rate should be positive".into(),
+            ));
+        }
+        Ok(Self::new(RecordRate::Hz(hz)))
+    }
+
+    /// samples recorded so far. the trajectory of the last flight is kept until the ball is activated again
+    pub fn trajectory(&self) -> &Trajectory {
+        &self.trajectory
+    }
+
+    pub fn samples(&self) -> &[TrajectorySample] {
+        self.trajectory.samples()
+    }
+
+    /// whether the ball is still in flight
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    pub(crate) fn start(&mut self, release: TrajectorySample) {
+        self.trajectory = Trajectory::default();
+        self.trajectory.push(release);
+        self.next_sample_time = release.time;
+        self.recording = true;
+        self.advance(release.time);
+    }
+
+    pub(crate) fn record(&mut self, sample: TrajectorySample) {
+        match self.rate.interval() {
+            None => self.trajectory.push(sample),
+            Some(_) => {
+                // half a step of tolerance for the rounding of the accumulated step times
+                if sample.time >= self.next_sample_time - T_STEP / 2. {
+                    self.trajectory.push(sample);
+                    self.advance(sample.time);
+                }
+            }
+        }
+    }
+
+    // returns the trajectory once the flight is over
+    pub(crate) fn finish(&mut self) -> Option<Trajectory> {
+        if self.recording {
+            self.recording = false;
+            Some(self.trajectory.clone())
+        } else {
+            None
+        }
+    }
+
+    // moves the next sample time past `time` by whole sample intervals
+    fn advance(&mut self, time: f64) {
+        if let Some(interval) = self.rate.interval() {
+            let behind = time - (self.next_sample_time - T_STEP / 2.);
+            if behind >= 0. {
+                self.next_sample_time += ((behind / interval).floor() + 1.) * interval;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // records 0.1 s of flight in steps of `T_STEP`
+    fn record(rate: RecordRate) -> Vec<f64> {
+        let mut recorder = TrajectoryRecorder::new(rate);
+        recorder.start(TrajectorySample::default());
+        for step in 1..=100 {
+            recorder.record(TrajectorySample {
+                time: step as f64 * T_STEP,
+                ..Default::default()
+            });
+        }
+        recorder
+            .finish()
+            .unwrap()
+            .samples()
+            .iter()
+            .map(|sample| sample.time)
+            .collect()
+    }
+
+    #[test]
+    fn fixed_rate_samples_at_the_rate() {
+        let times = record(RecordRate::Hz(100.));
+        assert_eq!(times.len(), 11);
+        for (index, time) in times.iter().enumerate() {
+            assert!((time - index as f64 * 0.01).abs() < 1e-9, "{times:?}");
+        }
+    }
+
+    #[test]
+    fn invalid_rates_record_every_step() {
+        for hz in [0., -10., f64::NAN, f64::INFINITY] {
+            assert_eq!(record(RecordRate::Hz(hz)).len(), 101);
+            assert!(TrajectoryRecorder::with_rate(hz).is_err());
+        }
+        // rates above the step rate record every step as well
+        assert_eq!(record(RecordRate::Hz(1e300)).len(), 101);
+    }
+
+    fn trajectory() -> Trajectory {
+        Trajectory {
+            samples: (0..3)
+                .map(|i| {
+                    let i = i as f32;
+                    TrajectorySample {
+                        time: i as f64 * 0.01,
+                        position: Vec3::new(0.1, 1.5, 18.) - Vec3::Z * i,
+                        velocity: Vec3::new(0.5, -0.2, -40.),
+                        spin: Vec3::new(250., 0.1, -2.),
+                        accelerations: ForceComponents {
+                            drag: Vec3::new(0., 0.3, 9.) * i,
+                            magnus: Vec3::new(-1., 4.5, 0.) * i,
+                            ssw: Vec3::new(0.25, -0.125, 0.) * i,
+                            gravity: Vec3::NEG_Y * 9.81,
+                        },
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn csv_round_trips_with_the_column_header() {
+        let trajectory = trajectory();
+        let mut csv = vec![];
+        trajectory.write_csv(&mut csv).unwrap();
+
+        let mut reader = csv::Reader::from_reader(csv.as_slice());
+        assert_eq!(reader.headers().unwrap(), TRAJECTORY_COLUMNS.as_slice());
+        let samples = reader
+            .deserialize()
+            .collect::<std::result::Result<Vec<TrajectorySample>, _>>()
+            .unwrap();
+        assert_eq!(samples, trajectory.samples);
+    }
+
+    #[test]
+    fn empty_csv_has_the_column_header() {
+        let mut csv = vec![];
+        Trajectory::default().write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            format!("{}\n", TRAJECTORY_COLUMNS.join(","))
+        );
+    }
+
+    #[test]
+    fn json_lines_round_trip_with_the_column_names() {
+        let trajectory = trajectory();
+        let mut json = vec![];
+        trajectory.write_json_lines(&mut json).unwrap();
+
+        let lines = String::from_utf8(json).unwrap();
+        let samples = lines
+            .lines()
+            .map(|line| {
+                let row = serde_json::from_str::<serde_json::Value>(line).unwrap();
+                let keys = row.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+                let mut columns = TRAJECTORY_COLUMNS.map(String::from).to_vec();
+                columns.sort();
+                assert_eq!(keys, columns);
+                serde_json::from_value::<TrajectorySample>(row).unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(samples, trajectory.samples);
+    }
+}